@@ -0,0 +1,52 @@
+mod common;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use quoridor::{
+    ai::{
+        moving::MoveOnly,
+        random::{Random, RandomMoving},
+        wall::{WallFirstMax, WallFirstMinmax},
+    },
+    quoridor::run::{run, AI},
+};
+
+fn bench_play<A: AI + Default>(c: &mut Criterion, name: &str) {
+    let mut group = c.benchmark_group(name);
+    for position in &common::positions() {
+        let mut ai = A::default();
+        group.bench_with_input(
+            BenchmarkId::from_parameter(position.name),
+            position,
+            |b, p| b.iter(|| ai.play(black_box(&p.board), &p.player_one, &p.player_two)),
+        );
+    }
+    group.finish();
+}
+
+fn play(c: &mut Criterion) {
+    bench_play::<MoveOnly>(c, "MoveOnly::play");
+    bench_play::<Random>(c, "Random::play");
+    bench_play::<RandomMoving>(c, "RandomMoving::play");
+    bench_play::<WallFirstMax>(c, "WallFirstMax::play");
+    bench_play::<WallFirstMinmax>(c, "WallFirstMinmax::play");
+}
+
+fn full_games(c: &mut Criterion) {
+    let mut group = c.benchmark_group("run");
+    group.sample_size(10);
+
+    group.bench_function("WallFirstMax vs MoveOnly", |b| {
+        b.iter(|| run(WallFirstMax::default(), MoveOnly::default()))
+    });
+    group.bench_function("WallFirstMax vs Random", |b| {
+        b.iter(|| run(WallFirstMax::default(), Random::default()))
+    });
+    group.bench_function("WallFirstMax vs WallFirstMax", |b| {
+        b.iter(|| run(WallFirstMax::default(), WallFirstMax::default()))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, play, full_games);
+criterion_main!(benches);
@@ -0,0 +1,106 @@
+mod common;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use quoridor::quoridor::{board, distance::distance_map};
+
+fn board_functions(c: &mut Criterion) {
+    let positions = common::positions();
+
+    let mut group = c.benchmark_group("get_valid_moves");
+    for position in &positions {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(position.name),
+            position,
+            |b, p| {
+                b.iter(|| board::get_valid_moves(black_box(&p.board), &p.player_one, &p.player_two))
+            },
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("get_valid_walls");
+    for position in &positions {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(position.name),
+            position,
+            |b, p| {
+                b.iter(|| board::get_valid_walls(black_box(&p.board), &p.player_one, &p.player_two))
+            },
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("can_place_wall");
+    for position in &positions {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(position.name),
+            position,
+            |b, p| {
+                b.iter(|| {
+                    board::can_place_wall(
+                        black_box(&p.board),
+                        &p.player_one,
+                        &p.player_two,
+                        black_box((false, 4, 4)),
+                    )
+                })
+            },
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("get_path_to_goal");
+    for position in &positions {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(position.name),
+            position,
+            |b, p| {
+                b.iter(|| {
+                    board::get_path_to_goal(black_box(&p.board), &p.player_one, &p.player_two)
+                })
+            },
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("get_best_max_walls");
+    for position in &positions {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(position.name),
+            position,
+            |b, p| {
+                b.iter(|| {
+                    board::get_best_max_walls(black_box(&p.board), &p.player_one, &p.player_two)
+                })
+            },
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("get_best_minmax_walls");
+    for position in &positions {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(position.name),
+            position,
+            |b, p| {
+                b.iter(|| {
+                    board::get_best_minmax_walls(black_box(&p.board), &p.player_one, &p.player_two)
+                })
+            },
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("distance_map");
+    for position in &positions {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(position.name),
+            position,
+            |b, p| b.iter(|| distance_map(black_box(&p.board), p.player_one.end_y)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, board_functions);
+criterion_main!(benches);
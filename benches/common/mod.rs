@@ -0,0 +1,91 @@
+use quoridor::quoridor::{
+    board::{self, Board},
+    player::Player,
+};
+
+pub struct Position {
+    pub name: &'static str,
+    pub board: Board,
+    pub player_one: Player,
+    pub player_two: Player,
+}
+
+fn position(
+    name: &'static str,
+    walls: &[(bool, usize, usize)],
+    (x_one, y_one, walls_one): (usize, usize, usize),
+    (x_two, y_two, walls_two): (usize, usize, usize),
+) -> Position {
+    let mut board = board::create_new_board();
+    for &wall in walls {
+        board::place_wall(&mut board, wall);
+    }
+
+    Position {
+        name,
+        board,
+        player_one: Player {
+            x: x_one,
+            y: y_one,
+            end_y: board::get_board_height() - 1,
+            walls: walls_one,
+        },
+        player_two: Player {
+            x: x_two,
+            y: y_two,
+            end_y: 0,
+            walls: walls_two,
+        },
+    }
+}
+
+/// Fixed positions the benchmarks run on, from the opening to a crowded endgame.
+pub fn positions() -> Vec<Position> {
+    vec![
+        position("start", &[], (4, 0, 10), (4, 8, 10)),
+        position(
+            "early",
+            &[(false, 3, 6), (false, 4, 1)],
+            (4, 2, 9),
+            (4, 6, 9),
+        ),
+        position(
+            "jump",
+            &[(false, 3, 3), (true, 5, 4), (false, 2, 6)],
+            (4, 4, 8),
+            (4, 5, 9),
+        ),
+        position(
+            "midgame",
+            &[
+                (false, 0, 5),
+                (false, 2, 5),
+                (true, 3, 4),
+                (false, 5, 2),
+                (false, 7, 2),
+                (true, 6, 6),
+            ],
+            (3, 3, 7),
+            (5, 6, 7),
+        ),
+        position(
+            "walled",
+            &[
+                (false, 0, 4),
+                (false, 2, 4),
+                (false, 4, 4),
+                (true, 5, 3),
+                (false, 6, 2),
+                (true, 7, 5),
+                (false, 1, 1),
+                (false, 3, 6),
+                (true, 2, 6),
+                (false, 5, 7),
+                (true, 0, 2),
+                (false, 6, 5),
+            ],
+            (2, 3, 4),
+            (6, 6, 2),
+        ),
+    ]
+}
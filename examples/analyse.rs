@@ -0,0 +1,34 @@
+use quoridor::{
+    ai::{
+        analysis::{analyse, format_table},
+        eval::WeightedEval,
+        search::Search,
+    },
+    quoridor::notation,
+};
+
+use std::{env, time::Duration};
+
+fn main() {
+    // `"<position>" [depth]`, the position either like `1 e1:10 e9:10 d4h` or as the turns
+    // played from the start. A depth of 0 only evaluates the positions after every turn
+    let args: Vec<String> = env::args().skip(1).collect();
+    let position = args.first().map_or("", String::as_str);
+    let depth: usize = args
+        .get(1)
+        .map(|depth| depth.parse().expect("the depth must be a number"))
+        .unwrap_or(2);
+
+    let game = notation::parse_game(position).unwrap_or_else(|error| panic!("{}", error));
+
+    let analyses = if depth == 0 {
+        analyse(&game, &mut WeightedEval::default())
+    } else {
+        let mut search = Search::new(WeightedEval::default(), Duration::MAX);
+        search.max_depth = depth;
+        analyse(&game, &mut search)
+    };
+
+    println!("{}", notation::format_position(&game));
+    print!("{}", format_table(&analyses));
+}
@@ -0,0 +1,33 @@
+use quoridor::{
+    ai::{
+        annotate::{annotate, write_record, Thresholds},
+        eval::WeightedEval,
+        search::Search,
+    },
+    quoridor::notation,
+};
+
+use std::{env, fs, time::Duration};
+
+fn main() {
+    // `<records> [depth]`, one game per line, prints every game annotated
+    let args: Vec<String> = env::args().skip(1).collect();
+    let records = fs::read_to_string(args.first().expect("usage: annotate <records> [depth]"))
+        .expect("can't read the records");
+    let depth = args
+        .get(1)
+        .map(|depth| depth.parse().expect("the depth must be a number"))
+        .unwrap_or(3);
+
+    let mut search = Search::new(WeightedEval::default(), Duration::MAX);
+    search.max_depth = depth;
+
+    for (index, line) in records.lines().enumerate() {
+        let turns = notation::parse_record(line)
+            .unwrap_or_else(|error| panic!("line {}: {}", index + 1, error));
+        match annotate(&turns, &mut search, &Thresholds::default()) {
+            Ok(annotations) => println!("{}", write_record(&annotations)),
+            Err(illegal) => panic!("line {}: illegal turn {}", index + 1, illegal.turn),
+        }
+    }
+}
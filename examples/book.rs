@@ -0,0 +1,39 @@
+use quoridor::{
+    ai::{book::Book, random::Random, wall::WallFirstMax},
+    quoridor::notation,
+};
+
+use std::{env, fs};
+
+fn main() {
+    // `selfplay <games> <book>` or `records <file> <book>`, a record being one game per line
+    let args: Vec<String> = env::args().skip(1).collect();
+    let plies = 8;
+
+    let book = match args.first().map(String::as_str) {
+        Some("selfplay") => {
+            let games = args[1]
+                .parse()
+                .expect("the number of games must be a number");
+            Book::from_self_play(games, plies, 0, WallFirstMax::with_seed, Random::with_seed)
+        }
+        Some("records") => {
+            let mut book = Book::new();
+            let records = fs::read_to_string(&args[1]).expect("can't read the records");
+
+            for (index, line) in records.lines().enumerate() {
+                let turns = notation::parse_turns(line)
+                    .unwrap_or_else(|error| panic!("line {}: {}", index + 1, error));
+                if let Err(illegal) = book.add_game(&turns, None, plies) {
+                    panic!("line {}: illegal turn {}", index + 1, illegal.turn);
+                }
+            }
+
+            book
+        }
+        _ => panic!("usage: book selfplay <games> <book> | book records <file> <book>"),
+    };
+
+    book.save(&args[2]).expect("can't write the book");
+    println!("{} positions written to {}", book.len(), args[2]);
+}
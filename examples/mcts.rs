@@ -0,0 +1,31 @@
+use quoridor::{
+    ai::{
+        mcts::{Mcts, Parallelism},
+        wall::WallFirstMax,
+    },
+    quoridor::run::run,
+};
+
+use std::env;
+
+fn main() {
+    // Playouts per turn, threads and `root` or `tree` parallelism
+    let mut args = env::args().skip(1);
+    let mut mcts = Mcts::default();
+
+    if let Some(playouts) = args.next() {
+        mcts.playouts = playouts.parse().expect("playouts must be a number");
+    }
+    if let Some(threads) = args.next() {
+        mcts.threads = threads.parse().expect("threads must be a number");
+    }
+    mcts.parallelism = match args.next().as_deref() {
+        None | Some("root") => Parallelism::Root,
+        Some("tree") => Parallelism::Tree,
+        Some(other) => panic!("unknown parallelism `{}`", other),
+    };
+
+    let result = run(mcts, WallFirstMax::default());
+
+    println!("{:?}", result);
+}
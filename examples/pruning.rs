@@ -0,0 +1,60 @@
+use quoridor::{
+    ai::{pruning::WallPruning, random::Random, wall::WallFirstMax},
+    quoridor::{game::Game, observer::Recorder, run::run_with_observers},
+};
+
+fn main() {
+    // Positions from a few games, both sides placing walls
+    let mut games = Vec::new();
+    for seed in 0..10 {
+        let mut recorder = Recorder::default();
+        run_with_observers(
+            WallFirstMax::with_seed(seed),
+            Random::with_seed(seed),
+            &mut [&mut recorder],
+        );
+
+        let mut game = Game::new();
+        for (turn, _) in recorder.turns {
+            games.push(game.clone());
+            game.apply(turn);
+        }
+    }
+
+    let options = [
+        (
+            "paths",
+            WallPruning {
+                paths: true,
+                ..WallPruning::OFF
+            },
+        ),
+        (
+            "adjacent",
+            WallPruning {
+                adjacent: true,
+                ..WallPruning::OFF
+            },
+        ),
+        (
+            "near pawns (1)",
+            WallPruning {
+                near_pawns: Some(1),
+                ..WallPruning::OFF
+            },
+        ),
+        (
+            "near pawns (2)",
+            WallPruning {
+                near_pawns: Some(2),
+                ..WallPruning::OFF
+            },
+        ),
+        ("default", WallPruning::default()),
+        ("off", WallPruning::OFF),
+    ];
+
+    for (name, pruning) in options {
+        println!("{:>16}: {}", name, pruning.stats(&games));
+    }
+}
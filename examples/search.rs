@@ -0,0 +1,26 @@
+use quoridor::{
+    ai::{eval::WeightedEval, search::Search, wall::WallFirstMax},
+    quoridor::run::run,
+};
+
+use std::{env, time::Duration};
+
+fn main() {
+    // Milliseconds per turn and threads
+    let time_limit = env::args()
+        .nth(1)
+        .map(|ms| ms.parse().expect("the time limit must be a number"))
+        .unwrap_or(500);
+    let threads = env::args()
+        .nth(2)
+        .map(|threads| threads.parse().expect("threads must be a number"))
+        .unwrap_or(1);
+
+    let mut search = Search::new(WeightedEval::default(), Duration::from_millis(time_limit));
+    search.threads = threads;
+    search.verbose = true;
+
+    let result = run(search, WallFirstMax::default());
+
+    println!("{:?}", result);
+}
@@ -0,0 +1,60 @@
+use quoridor::{
+    ai::wall::{WallFirstMax, WallFirstMinmax},
+    quoridor::{notation, run::run_with_observers, stats::Statistics},
+    render::svg::Svg,
+};
+
+use std::{env, fs, path::Path};
+
+fn main() {
+    // `selfplay <games> <directory>` or `records <file> <directory>`, a record being one game per
+    // line. Writes the CSV files and the heatmaps to the directory
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut stats = Statistics::new();
+
+    match args.first().map(String::as_str) {
+        Some("selfplay") => {
+            let games = args[1]
+                .parse()
+                .expect("the number of games must be a number");
+            for seed in 0..games {
+                run_with_observers(
+                    WallFirstMax::with_seed(seed),
+                    WallFirstMinmax::with_seed(seed + 1),
+                    &mut [&mut stats],
+                );
+            }
+        }
+        Some("records") => {
+            let records = fs::read_to_string(&args[1]).expect("can't read the records");
+
+            for (index, line) in records.lines().enumerate() {
+                let turns = notation::parse_record(line)
+                    .unwrap_or_else(|error| panic!("line {}: {}", index + 1, error));
+                if let Err(illegal) = stats.add_game(&turns, None) {
+                    panic!("line {}: illegal turn {}", index + 1, illegal.turn);
+                }
+            }
+        }
+        _ => panic!("usage: stats selfplay <games> <directory> | stats records <file> <directory>"),
+    }
+
+    let directory = Path::new(&args[2]);
+    stats
+        .write_csv(directory)
+        .expect("can't write the statistics");
+
+    let svg = Svg {
+        labels: true,
+        highlight: false,
+    };
+    let heatmaps = [
+        ("walls.svg", svg.heatmap(&[], &stats.wall_heat())),
+        ("tiles.svg", svg.heatmap(&stats.tile_heat(None), &[])),
+    ];
+    for (name, heatmap) in heatmaps {
+        fs::write(directory.join(name), heatmap).expect("can't write the heatmaps");
+    }
+
+    print!("{}", stats.summary_csv());
+}
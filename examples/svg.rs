@@ -0,0 +1,50 @@
+use quoridor::{
+    ai::{moving::MoveOnly, wall::WallFirstMax},
+    quoridor::{board, game::Game, observer::Recorder, run::run_with_observers},
+    render::svg::{Annotations, Svg},
+};
+
+use std::{fs, time::Duration};
+
+fn main() {
+    let mut recorder = Recorder::default();
+    run_with_observers(
+        WallFirstMax::default(),
+        MoveOnly::default(),
+        &mut [&mut recorder],
+    );
+
+    let turns: Vec<_> = recorder.turns.iter().map(|&(turn, _)| turn).collect();
+    let svg = Svg {
+        labels: true,
+        highlight: true,
+    };
+
+    // The position before the winning turn, with that turn suggested and both shortest paths
+    // drawn in
+    let game = Game::replay(&turns[..turns.len() - 1]).unwrap();
+    let annotations = Annotations {
+        suggested_turn: turns.last().copied(),
+        paths: vec![
+            board::get_path_to_goal(&game.board, &game.player_one, &game.player_two),
+            board::get_path_to_goal(&game.board, &game.player_two, &game.player_one),
+        ],
+    };
+    fs::write(
+        "position.svg",
+        svg.render_annotated(
+            &game,
+            turns.get(turns.len().wrapping_sub(2)).copied(),
+            &annotations,
+        ),
+    )
+    .unwrap();
+
+    fs::write(
+        "game.svg",
+        svg.animate(&turns, Duration::from_millis(500)).unwrap(),
+    )
+    .unwrap();
+
+    println!("Wrote position.svg and game.svg ({} turns)", turns.len());
+}
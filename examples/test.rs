@@ -1,9 +1,49 @@
-use quoridor::{ai::moving::MoveOnly, quoridor::run::run};
+use quoridor::{
+    ai::moving::MoveOnly,
+    quoridor::{
+        observer::GameObserver,
+        run::{run, run_with_observers},
+    },
+    render::{ascii::Ascii, compact::Compact, unicode::Unicode, Printer},
+};
+
+use std::{env, time::Duration};
 
 fn main() {
     let player_one = MoveOnly::default();
     let player_two = MoveOnly::default();
-    let result = run(player_one, player_two);
+
+    // Pass `unicode`, `ascii` or `compact` to watch the game
+    let delay = Duration::from_secs(1);
+    let mut printer: Option<Box<dyn GameObserver>> = match env::args().nth(1).as_deref() {
+        Some("unicode") => Some(Box::new(Printer::new(
+            Unicode {
+                labels: true,
+                highlight: true,
+            },
+            delay,
+        ))),
+        Some("ascii") => Some(Box::new(Printer::new(
+            Ascii {
+                labels: true,
+                highlight: true,
+            },
+            delay,
+        ))),
+        Some("compact") => Some(Box::new(Printer::new(
+            Compact {
+                labels: true,
+                highlight: true,
+            },
+            delay,
+        ))),
+        _ => None,
+    };
+
+    let result = match printer.as_deref_mut() {
+        Some(printer) => run_with_observers(player_one, player_two, &mut [printer]),
+        None => run(player_one, player_two),
+    };
 
     println!("{} {}", result.winner, result.turns);
 }
@@ -0,0 +1,18 @@
+use quoridor::{
+    ai::{random::Random, wall::WallFirstMax},
+    quoridor::tournament::play_match,
+};
+
+use std::env;
+
+fn main() {
+    // Pass a seed to replay a previous match
+    let seed = env::args()
+        .nth(1)
+        .map(|seed| seed.parse().expect("the seed must be a number"))
+        .unwrap_or_else(rand::random);
+
+    let result = play_match(20, seed, WallFirstMax::with_seed, Random::with_seed);
+
+    println!("{}", result);
+}
@@ -0,0 +1,38 @@
+use quoridor::ai::{
+    eval::WeightedEval,
+    tune::{self, GeneticOptions, TexelOptions},
+};
+
+use std::env;
+
+// cargo run --release --example tune -- texel weights.txt
+// cargo run --release --example tune -- genetic weights.txt
+fn main() {
+    let method = env::args().nth(1).unwrap_or_else(|| "texel".to_string());
+    let output = env::args()
+        .nth(2)
+        .unwrap_or_else(|| "weights.txt".to_string());
+    let start = WeightedEval::default();
+
+    let weights = match method.as_str() {
+        "texel" => {
+            let samples = tune::self_play(50, 0, &start, 0.1);
+            let options = TexelOptions::default();
+            let weights = tune::texel(&samples, &start, &options);
+
+            println!(
+                "{} positions, error {:.4} -> {:.4}",
+                samples.len(),
+                tune::texel_error(&samples, &start, options.scale),
+                tune::texel_error(&samples, &weights, options.scale)
+            );
+            weights
+        }
+        "genetic" => tune::genetic(&start, &GeneticOptions::default()),
+        _ => panic!("unknown method {}, use texel or genetic", method),
+    };
+
+    print!("{}", weights);
+    weights.save(&output).unwrap();
+    println!("saved to {}", output);
+}
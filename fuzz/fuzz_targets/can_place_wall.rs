@@ -0,0 +1,29 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use quoridor::quoridor::{
+    board,
+    game::Game,
+    run::{Turn, WallData},
+};
+
+// Plays the setup walls that are legal, then checks an arbitrary (possibly off-board) wall
+fuzz_target!(|data: (Vec<WallData>, WallData)| {
+    let (setup, wall) = data;
+    let mut game = Game::new();
+
+    for setup_wall in setup {
+        if game.is_legal(Turn::Wall(setup_wall)) {
+            game.apply(Turn::Wall(setup_wall));
+        }
+    }
+
+    let (player, enemy) = game.players();
+    if board::can_place_wall(&game.board, player, enemy, wall) {
+        let mut board = game.board.clone();
+        board::place_wall(&mut board, wall);
+
+        assert!(!board::get_path_to_goal(&board, player, enemy).is_empty());
+        assert!(!board::get_path_to_goal(&board, enemy, player).is_empty());
+    }
+});
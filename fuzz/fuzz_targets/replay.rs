@@ -0,0 +1,34 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use quoridor::quoridor::{board, game::Game, run::Turn};
+
+fn to_turn((wall, vert, x, y): (bool, bool, u8, u8)) -> Turn {
+    if wall {
+        Turn::Wall((vert, x as usize, y as usize))
+    } else {
+        Turn::Move((x as usize, y as usize))
+    }
+}
+
+// Replays arbitrary turn sequences, checking that no legal turn ever seals a pawn off
+fuzz_target!(|data: Vec<(bool, bool, u8, u8)>| {
+    let turns: Vec<Turn> = data.into_iter().map(to_turn).collect();
+
+    let legal = match Game::replay(&turns) {
+        Ok(_) => &turns[..],
+        Err(illegal) => &turns[..illegal.index],
+    };
+
+    let mut game = Game::new();
+    for &turn in legal {
+        game.apply(turn);
+
+        assert!(game.player_one.walls + game.player_two.walls <= 20);
+        if game.winner().is_none() {
+            let (player, enemy) = game.players();
+            assert!(!board::get_path_to_goal(&game.board, player, enemy).is_empty());
+            assert!(!board::get_path_to_goal(&game.board, enemy, player).is_empty());
+        }
+    }
+});
@@ -0,0 +1,46 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use quoridor::quoridor::{
+    board,
+    game::Game,
+    run::{MoveData, Turn, WallData},
+};
+
+// Puts both pawns on arbitrary tiles next to arbitrary walls and checks the generated moves
+fuzz_target!(|data: (MoveData, MoveData, Vec<WallData>, MoveData)| {
+    let (one, two, walls, target) = data;
+    let mut game = Game::new();
+
+    (game.player_one.x, game.player_one.y) = (
+        one.0 % board::get_board_width(),
+        one.1 % board::get_board_height(),
+    );
+    (game.player_two.x, game.player_two.y) = (
+        two.0 % board::get_board_width(),
+        two.1 % board::get_board_height(),
+    );
+    if (game.player_one.x, game.player_one.y) == (game.player_two.x, game.player_two.y) {
+        return;
+    }
+
+    for wall in walls {
+        let (player, enemy) = game.players();
+        if board::can_place_wall(&game.board, player, enemy, wall) {
+            board::place_wall(&mut game.board, wall);
+        }
+    }
+
+    let (player, enemy) = game.players();
+    for turn in board::get_valid_moves(&game.board, player, enemy) {
+        let Turn::Move((x, y)) = turn else {
+            panic!("get_valid_moves returned {:?}", turn);
+        };
+
+        assert!(x < board::get_board_width() && y < board::get_board_height());
+        assert!((x, y) != (player.x, player.y) && (x, y) != (enemy.x, enemy.y));
+        assert!(x.abs_diff(player.x) + y.abs_diff(player.y) <= 2);
+    }
+
+    let _ = game.is_legal(Turn::Move(target));
+});
@@ -0,0 +1,110 @@
+//! Every legal turn of a position with its score, for finding out what an AI thinks of it.
+
+use super::{
+    super::quoridor::{board, game::Game, run::Turn},
+    eval::Evaluator,
+    search::{Search, WIN},
+};
+
+use std::fmt::Write;
+
+/// Scores positions for `analyse`.
+pub trait Analyser {
+    /// Scores `game`, the position right after a turn that didn't end the game, for the player
+    /// who played it. Also returns the best turns from `game` on, as far as they are known.
+    fn score(&mut self, game: &Game) -> (f64, Vec<Turn>);
+}
+
+/// Evaluators only look at the position itself, their variations are empty.
+impl<E: Evaluator> Analyser for E {
+    fn score(&mut self, game: &Game) -> (f64, Vec<Turn>) {
+        let (enemy, player) = game.players();
+        (self.evaluate(&game.board, player, enemy), Vec::new())
+    }
+}
+
+impl<E: Evaluator + Sync> Analyser for Search<E> {
+    fn score(&mut self, game: &Game) -> (f64, Vec<Turn>) {
+        let info = self.search(game);
+        (-info.score, info.pv)
+    }
+}
+
+/// What a turn leads to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TurnAnalysis {
+    pub turn: Turn,
+    /// From the point of view of the player playing `turn`, `WIN` if it reaches the goal.
+    pub score: f64,
+    /// Steps left to the goal for the player playing `turn`, after it.
+    pub path: usize,
+    /// Steps left to the goal for the enemy, after `turn`.
+    pub enemy_path: usize,
+    /// Principal variation: `turn` followed by the best replies found.
+    pub pv: Vec<Turn>,
+}
+
+/// Every legal turn of the player to move in `game`, best first.
+pub fn analyse<A: Analyser + ?Sized>(game: &Game, analyser: &mut A) -> Vec<TurnAnalysis> {
+    let mut analyses: Vec<TurnAnalysis> = game
+        .get_valid_turns()
+        .into_iter()
+        .map(|turn| {
+            let mut next = game.clone();
+            next.apply(turn);
+
+            let (enemy, player) = next.players();
+            let steps = |player, enemy| {
+                board::get_path_to_goal(&next.board, player, enemy)
+                    .len()
+                    .saturating_sub(1)
+            };
+            let path = steps(player, enemy);
+            let enemy_path = steps(enemy, player);
+
+            let (score, replies) = if next.winner().is_some() {
+                (WIN, Vec::new())
+            } else {
+                analyser.score(&next)
+            };
+
+            let mut pv = vec![turn];
+            pv.extend(replies);
+
+            TurnAnalysis {
+                turn,
+                score,
+                path,
+                enemy_path,
+                pv,
+            }
+        })
+        .collect();
+
+    analyses.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    analyses
+}
+
+/// `analyses` as a table ranked by score, one line per turn under a header.
+pub fn format_table(analyses: &[TurnAnalysis]) -> String {
+    let mut out = format!(
+        "{:>4}  {:<5} {:>10} {:>5} {:>6}  pv\n",
+        "#", "turn", "score", "path", "enemy"
+    );
+    for (rank, analysis) in analyses.iter().enumerate() {
+        let pv: Vec<String> = analysis.pv.iter().map(|turn| turn.to_string()).collect();
+        let _ = writeln!(
+            out,
+            "{:>4}  {:<5} {:>10.2} {:>5} {:>6}  {}",
+            rank + 1,
+            analysis.turn.to_string(),
+            analysis.score,
+            analysis.path,
+            analysis.enemy_path,
+            pv.join(" ")
+        );
+    }
+
+    out
+}
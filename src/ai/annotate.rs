@@ -0,0 +1,189 @@
+//! Finds the turns where a game was lost: every position of a record is scored and turns that
+//! lose too much of the score are flagged, together with the turn the analyser preferred.
+
+use super::{
+    super::quoridor::{
+        game::{Game, IllegalTurn},
+        run::Turn,
+    },
+    analysis::Analyser,
+    search::{WIN, WIN_THRESHOLD},
+};
+
+use std::fmt::Write;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Judgement {
+    Inaccuracy,
+    Mistake,
+    Blunder,
+}
+
+impl Judgement {
+    /// The usual annotation symbol.
+    pub fn glyph(self) -> &'static str {
+        match self {
+            Judgement::Inaccuracy => "?!",
+            Judgement::Mistake => "?",
+            Judgement::Blunder => "??",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Judgement::Inaccuracy => "inaccuracy",
+            Judgement::Mistake => "mistake",
+            Judgement::Blunder => "blunder",
+        }
+    }
+}
+
+/// How much score a turn has to lose to be flagged. The defaults are meant for evaluations
+/// counting path steps, like `WeightedEval::default`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Thresholds {
+    pub inaccuracy: f64,
+    pub mistake: f64,
+    pub blunder: f64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            inaccuracy: 1.0,
+            mistake: 2.0,
+            blunder: 4.0,
+        }
+    }
+}
+
+impl Thresholds {
+    pub fn judge(&self, swing: f64) -> Option<Judgement> {
+        if swing >= self.blunder {
+            Some(Judgement::Blunder)
+        } else if swing >= self.mistake {
+            Some(Judgement::Mistake)
+        } else if swing >= self.inaccuracy {
+            Some(Judgement::Inaccuracy)
+        } else {
+            None
+        }
+    }
+}
+
+/// One turn of an annotated game. Scores are from the point of view of the player playing it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Annotation {
+    pub turn: Turn,
+    /// The score of the position before the turn.
+    pub before: f64,
+    /// The score of the position after the turn.
+    pub after: f64,
+    pub judgement: Option<Judgement>,
+    /// What the analyser would have played instead, only for flagged turns.
+    pub better: Option<Turn>,
+}
+
+impl Annotation {
+    /// How much score the turn lost. Turns keeping a won (or lost) game won (or lost) lose
+    /// nothing, whether or not they were the quickest way.
+    pub fn swing(&self) -> f64 {
+        let decided = |score: f64| {
+            if score >= WIN_THRESHOLD {
+                1
+            } else if score <= -WIN_THRESHOLD {
+                -1
+            } else {
+                0
+            }
+        };
+
+        let side = decided(self.before);
+        if side != 0 && side == decided(self.after) {
+            0.0
+        } else {
+            self.before - self.after
+        }
+    }
+}
+
+/// Scores every position of the game `turns` with `analyser` and judges every turn by the score
+/// it lost. Every position is scored once, so the analyser should look further than one turn,
+/// and only turns differing from the one the analyser prefers are flagged (an `Evaluator` never
+/// prefers any).
+pub fn annotate<A: Analyser + ?Sized>(
+    turns: &[Turn],
+    analyser: &mut A,
+    thresholds: &Thresholds,
+) -> Result<Vec<Annotation>, IllegalTurn> {
+    let mut game = Game::new();
+    // The score and best turn for the player to move
+    let (score, pv) = analyser.score(&game);
+    let mut current = (-score, pv.first().copied());
+    let mut annotations = Vec::with_capacity(turns.len());
+
+    for (index, &turn) in turns.iter().enumerate() {
+        if !game.is_legal(turn) {
+            return Err(IllegalTurn { index, turn });
+        }
+        game.apply(turn);
+
+        let next = if game.winner().is_some() {
+            (-WIN, None)
+        } else {
+            let (score, pv) = analyser.score(&game);
+            (-score, pv.first().copied())
+        };
+
+        let (before, best) = current;
+        let mut annotation = Annotation {
+            turn,
+            before,
+            after: -next.0,
+            judgement: None,
+            better: None,
+        };
+        if best.is_some_and(|best| best != turn) {
+            annotation.judgement = thresholds.judge(annotation.swing());
+            if annotation.judgement.is_some() {
+                annotation.better = best;
+            }
+        }
+
+        annotations.push(annotation);
+        current = next;
+    }
+
+    Ok(annotations)
+}
+
+/// Writes an annotated game as a record `notation::parse_record` reads: flagged turns get their
+/// symbol and a comment with both scores and the better turn.
+pub fn write_record(annotations: &[Annotation]) -> String {
+    let mut out = String::new();
+
+    for annotation in annotations {
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        write!(out, "{}", annotation.turn).unwrap();
+
+        if let Some(judgement) = annotation.judgement {
+            write!(
+                out,
+                "{} {{{} {:.2} -> {:.2}",
+                judgement.glyph(),
+                judgement.name(),
+                annotation.before,
+                annotation.after
+            )
+            .unwrap();
+            if let Some(better) = annotation.better {
+                write!(out, ", better {}", better).unwrap();
+            }
+            out.push('}');
+        }
+    }
+
+    out
+}
@@ -0,0 +1,313 @@
+use super::{
+    super::quoridor::{
+        board::Board,
+        game::{Game, IllegalTurn},
+        observer::Recorder,
+        player::Player,
+        run::{run_with_observers, Context, GameResult, Turn, AI},
+    },
+    tt::{decode_turn, encode_turn},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+const MAGIC: &[u8; 4] = b"QBK1";
+
+/// Opening theory: the turns played in a position, weighted by how often they were played.
+/// Positions are looked up by `Game::hash`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Book {
+    positions: HashMap<u64, Vec<(Turn, u32)>>,
+}
+
+impl Book {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of positions in the book.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// The turns known for `game` and their weights.
+    pub fn turns(&self, game: &Game) -> &[(Turn, u32)] {
+        self.positions
+            .get(&game.hash())
+            .map_or(&[], |turns| turns.as_slice())
+    }
+
+    /// A random book turn for `game`, more likely the higher its weight.
+    pub fn pick(&self, game: &Game, rng: &mut impl Rng) -> Option<Turn> {
+        let turns = self.turns(game);
+        let total: u32 = turns.iter().map(|&(_, weight)| weight).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut pick = rng.gen_range(0..total);
+        for &(turn, weight) in turns {
+            if pick < weight {
+                return Some(turn);
+            }
+            pick -= weight;
+        }
+
+        None
+    }
+
+    /// Adds `weight` to `turn` in `game`.
+    pub fn add(&mut self, game: &Game, turn: Turn, weight: u32) {
+        let turns = self.positions.entry(game.hash()).or_default();
+
+        match turns.iter_mut().find(|(known, _)| *known == turn) {
+            Some((_, known)) => *known = known.saturating_add(weight),
+            None => turns.push((turn, weight)),
+        }
+    }
+
+    /// Adds the first `plies` turns of a game record. With a `winner` only their turns are
+    /// added, with `None` the turns of both players.
+    pub fn add_game(
+        &mut self,
+        turns: &[Turn],
+        winner: Option<bool>,
+        plies: usize,
+    ) -> Result<(), IllegalTurn> {
+        let mut game = Game::new();
+
+        for (index, &turn) in turns.iter().enumerate().take(plies) {
+            if !game.is_legal(turn) {
+                return Err(IllegalTurn { index, turn });
+            }
+
+            if winner.is_none_or(|winner| winner == game.player_one_turn) {
+                self.add(&game, turn, 1);
+            }
+            game.apply(turn);
+        }
+
+        Ok(())
+    }
+
+    /// Builds a book from the first `plies` turns of the winners of `games` games between AIs
+    /// created from seeds derived from `seed`.
+    pub fn from_self_play<F, V>(
+        games: usize,
+        plies: usize,
+        seed: u64,
+        mut player_one: impl FnMut(u64) -> F,
+        mut player_two: impl FnMut(u64) -> V,
+    ) -> Self
+    where
+        F: AI,
+        V: AI,
+    {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut book = Self::new();
+
+        for _ in 0..games {
+            let mut recorder = Recorder::default();
+            let result = run_with_observers(
+                player_one(rng.gen()),
+                player_two(rng.gen()),
+                &mut [&mut recorder],
+            );
+
+            let turns: Vec<Turn> = recorder.turns.iter().map(|&(turn, _)| turn).collect();
+            // Forfeited games end in an illegal turn that never made it into the record
+            book.add_game(&turns, Some(result.winner), plies)
+                .expect("recorded turns are legal");
+        }
+
+        book
+    }
+
+    /// Writes the book in its binary format: `QBK1`, the number of positions and for every
+    /// position its hash, the number of turns and every turn with its weight, all little endian.
+    pub fn write(&self, mut out: impl Write) -> io::Result<()> {
+        let mut hashes: Vec<&u64> = self.positions.keys().collect();
+        hashes.sort();
+
+        out.write_all(MAGIC)?;
+        out.write_all(&(hashes.len() as u32).to_le_bytes())?;
+
+        for hash in hashes {
+            let turns = &self.positions[hash];
+            out.write_all(&hash.to_le_bytes())?;
+            out.write_all(&(turns.len() as u16).to_le_bytes())?;
+
+            for &(turn, weight) in turns {
+                out.write_all(&encode_turn(Some(turn)).to_le_bytes())?;
+                out.write_all(&weight.to_le_bytes())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn read(mut input: impl Read) -> io::Result<Self> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        let mut magic = [0; 4];
+        input.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("not an opening book"));
+        }
+
+        let mut book = Self::new();
+        let positions = read_u32(&mut input)?;
+
+        for _ in 0..positions {
+            let hash = read_u64(&mut input)?;
+            let count = read_u16(&mut input)?;
+            let mut turns = Vec::with_capacity(count as usize);
+
+            for _ in 0..count {
+                let turn = decode_turn(read_u16(&mut input)?)
+                    .ok_or_else(|| invalid("invalid turn in opening book"))?;
+                turns.push((turn, read_u32(&mut input)?));
+            }
+
+            book.positions.insert(hash, turns);
+        }
+
+        Ok(book)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write(&mut out)?;
+        out.flush()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+}
+
+fn read_u16(input: &mut impl Read) -> io::Result<u16> {
+    let mut bytes = [0; 2];
+    input.read_exact(&mut bytes)?;
+    Ok(u16::from_le_bytes(bytes))
+}
+
+fn read_u32(input: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    input.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(input: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    input.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Plays from `book` while it knows the position and leaves the rest to `inner`.
+pub struct BookPlayer<A, R: Rng = StdRng> {
+    pub book: Book,
+    pub inner: A,
+    rng: R,
+    /// Whether the last turn came from the book, `inner` can't ponder about those.
+    from_book: bool,
+}
+
+impl<A: AI> BookPlayer<A> {
+    pub fn new(book: Book, inner: A) -> Self {
+        Self::with_rng(book, inner, StdRng::from_entropy())
+    }
+
+    pub fn with_seed(book: Book, inner: A, seed: u64) -> Self {
+        Self::with_rng(book, inner, StdRng::seed_from_u64(seed))
+    }
+}
+
+impl<A: AI, R: Rng> BookPlayer<A, R> {
+    pub fn with_rng(book: Book, inner: A, rng: R) -> Self {
+        Self {
+            book,
+            inner,
+            rng,
+            from_book: false,
+        }
+    }
+}
+
+impl<A: AI, R: Rng> BookPlayer<A, R> {
+    fn book_turn(&mut self, board: &Board, player: &Player, enemy: &Player) -> Option<Turn> {
+        let game = Game::from_players(board, player, enemy);
+
+        // A book built from other games may hold turns that are illegal here after a collision
+        let turn = self
+            .book
+            .pick(&game, &mut self.rng)
+            .filter(|&turn| game.is_legal(turn));
+        self.from_book = turn.is_some();
+
+        turn
+    }
+}
+
+impl<A: AI, R: Rng> AI for BookPlayer<A, R> {
+    fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn {
+        match self.book_turn(board, player, enemy) {
+            Some(turn) => turn,
+            None => self.inner.play(board, player, enemy),
+        }
+    }
+
+    fn play_in_context(&mut self, context: &Context) -> Turn {
+        let (player, enemy) = context.players();
+        match self.book_turn(&context.game.board, player, enemy) {
+            Some(turn) => turn,
+            None => self.inner.play_in_context(context),
+        }
+    }
+
+    fn new_game(&mut self, context: &Context) {
+        self.inner.new_game(context);
+    }
+
+    fn opponent_played(&mut self, turn: Turn) {
+        self.inner.opponent_played(turn);
+    }
+
+    fn game_over(&mut self, result: &GameResult) {
+        self.inner.game_over(result);
+    }
+
+    fn ponder_turn(&self) -> Option<Turn> {
+        if self.from_book {
+            None
+        } else {
+            self.inner.ponder_turn()
+        }
+    }
+
+    fn ponder_start(&mut self, board: &Board, player: &Player, enemy: &Player, predicted: Turn) {
+        self.inner.ponder_start(board, player, enemy, predicted);
+    }
+
+    fn ponder_hit(&mut self) {
+        self.inner.ponder_hit();
+    }
+
+    fn ponder_miss(&mut self) {
+        self.inner.ponder_miss();
+    }
+
+    fn name(&self) -> String {
+        format!("{}+book", self.inner.name())
+    }
+}
@@ -0,0 +1,253 @@
+use super::super::quoridor::{
+    board::{self, Board},
+    distance::distance_map,
+    player::Player,
+};
+
+use std::{error::Error, fmt, fs, io, path::Path, str::FromStr};
+
+/// Scores a position for `player`, higher is better.
+pub trait Evaluator {
+    fn evaluate(&self, board: &Board, player: &Player, enemy: &Player) -> f64;
+}
+
+impl<E: Evaluator + ?Sized> Evaluator for &E {
+    fn evaluate(&self, board: &Board, player: &Player, enemy: &Player) -> f64 {
+        (**self).evaluate(board, player, enemy)
+    }
+}
+
+impl<E: Evaluator + ?Sized> Evaluator for Box<E> {
+    fn evaluate(&self, board: &Board, player: &Player, enemy: &Player) -> f64 {
+        (**self).evaluate(board, player, enemy)
+    }
+}
+
+/// Something about a position that can be put into a number, for `WeightedEval`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Feature {
+    /// Steps along the shortest path to the goal.
+    OwnPath,
+    EnemyPath,
+    /// Walls left to place.
+    OwnWalls,
+    EnemyWalls,
+    /// Rows between the pawn and the goal row, ignoring walls.
+    OwnGoalDistance,
+    EnemyGoalDistance,
+    /// `ln(1 + n)` of the number `n` of different shortest paths, ignoring pawns. Many
+    /// shortest paths make a path harder to block.
+    OwnShortestPaths,
+    EnemyShortestPaths,
+    /// Number of valid moves.
+    OwnMobility,
+    EnemyMobility,
+}
+
+impl Feature {
+    pub const ALL: [Feature; 10] = [
+        Feature::OwnPath,
+        Feature::EnemyPath,
+        Feature::OwnWalls,
+        Feature::EnemyWalls,
+        Feature::OwnGoalDistance,
+        Feature::EnemyGoalDistance,
+        Feature::OwnShortestPaths,
+        Feature::EnemyShortestPaths,
+        Feature::OwnMobility,
+        Feature::EnemyMobility,
+    ];
+
+    /// The name used in weight files.
+    pub fn name(self) -> &'static str {
+        match self {
+            Feature::OwnPath => "own_path",
+            Feature::EnemyPath => "enemy_path",
+            Feature::OwnWalls => "own_walls",
+            Feature::EnemyWalls => "enemy_walls",
+            Feature::OwnGoalDistance => "own_goal_distance",
+            Feature::EnemyGoalDistance => "enemy_goal_distance",
+            Feature::OwnShortestPaths => "own_shortest_paths",
+            Feature::EnemyShortestPaths => "enemy_shortest_paths",
+            Feature::OwnMobility => "own_mobility",
+            Feature::EnemyMobility => "enemy_mobility",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Feature> {
+        Feature::ALL
+            .into_iter()
+            .find(|feature| feature.name() == name)
+    }
+
+    pub fn value(self, board: &Board, player: &Player, enemy: &Player) -> f64 {
+        match self {
+            Feature::OwnPath => path_steps(board, player, enemy),
+            Feature::EnemyPath => path_steps(board, enemy, player),
+            Feature::OwnWalls => player.walls as f64,
+            Feature::EnemyWalls => enemy.walls as f64,
+            Feature::OwnGoalDistance => player.y.abs_diff(player.end_y) as f64,
+            Feature::EnemyGoalDistance => enemy.y.abs_diff(enemy.end_y) as f64,
+            Feature::OwnShortestPaths => count_shortest_paths(board, player).ln_1p(),
+            Feature::EnemyShortestPaths => count_shortest_paths(board, enemy).ln_1p(),
+            Feature::OwnMobility => board::get_valid_moves(board, player, enemy).len() as f64,
+            Feature::EnemyMobility => board::get_valid_moves(board, enemy, player).len() as f64,
+        }
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+fn path_steps(board: &Board, player: &Player, enemy: &Player) -> f64 {
+    board::get_path_len(board, player, enemy).saturating_sub(1) as f64
+}
+
+fn count_shortest_paths(board: &Board, player: &Player) -> f64 {
+    let map = distance_map(board, player.end_y);
+    let distances = map.as_slice();
+    let start = board::point_to_index(player.x, player.y);
+
+    if map.get(player.x, player.y).is_none() {
+        return 0.0;
+    }
+
+    // Every shortest path only ever steps to a tile one closer to the goal, so the tiles can be
+    // counted in order of decreasing distance
+    let mut order: Vec<usize> = (0..distances.len())
+        .filter(|&index| distances[index] <= distances[start])
+        .collect();
+    order.sort_by_key(|&index| std::cmp::Reverse(distances[index]));
+
+    let mut counts = vec![0.0; distances.len()];
+    counts[start] = 1.0;
+    let mut total = 0.0;
+
+    for index in order {
+        if counts[index] == 0.0 {
+            continue;
+        }
+        if distances[index] == 0 {
+            total += counts[index];
+            continue;
+        }
+
+        for neighbour in board::get_neighbours(board, index) {
+            if distances[neighbour] + 1 == distances[index] {
+                counts[neighbour] += counts[index];
+            }
+        }
+    }
+
+    total
+}
+
+/// Weighted sum of features.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeightedEval {
+    pub weights: Vec<(Feature, f64)>,
+}
+
+impl Default for WeightedEval {
+    /// Shortest path race: the enemy's path minus the own path.
+    fn default() -> Self {
+        Self::new(vec![(Feature::OwnPath, -1.0), (Feature::EnemyPath, 1.0)])
+    }
+}
+
+impl Evaluator for WeightedEval {
+    fn evaluate(&self, board: &Board, player: &Player, enemy: &Player) -> f64 {
+        self.weights
+            .iter()
+            .filter(|(_, weight)| *weight != 0.0)
+            .map(|(feature, weight)| weight * feature.value(board, player, enemy))
+            .sum()
+    }
+}
+
+impl WeightedEval {
+    pub fn new(weights: Vec<(Feature, f64)>) -> Self {
+        Self { weights }
+    }
+
+    pub fn weight(&self, feature: Feature) -> f64 {
+        self.weights
+            .iter()
+            .filter(|(f, _)| *f == feature)
+            .map(|(_, weight)| weight)
+            .sum()
+    }
+
+    /// Loads weights written in the format of `Display`, one `name = weight` per line.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+impl fmt::Display for WeightedEval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (feature, weight) in &self.weights {
+            writeln!(f, "{} = {}", feature, weight)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseWeightsError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseWeightsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseWeightsError {}
+
+impl FromStr for WeightedEval {
+    type Err = ParseWeightsError;
+
+    /// Empty lines and everything after a `#` are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut weights = Vec::new();
+
+        for (index, line) in s.lines().enumerate() {
+            let error = |message: String| ParseWeightsError {
+                line: index + 1,
+                message,
+            };
+
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (name, weight) = line
+                .split_once('=')
+                .ok_or_else(|| error(format!("expected `name = weight`, got `{}`", line)))?;
+            let feature = Feature::from_name(name.trim())
+                .ok_or_else(|| error(format!("unknown feature `{}`", name.trim())))?;
+            let weight = weight
+                .trim()
+                .parse()
+                .map_err(|_| error(format!("invalid weight `{}`", weight.trim())))?;
+
+            weights.push((feature, weight));
+        }
+
+        Ok(Self { weights })
+    }
+}
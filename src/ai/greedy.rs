@@ -0,0 +1,63 @@
+use super::{
+    super::quoridor::{
+        board::{self, Board},
+        player::Player,
+        run::{Turn, AI},
+    },
+    eval::{Evaluator, WeightedEval},
+};
+
+/// Looks one turn ahead and plays the turn leading to the best evaluated position.
+#[derive(Default)]
+pub struct Greedy<E: Evaluator = WeightedEval> {
+    pub evaluator: E,
+}
+
+impl<E: Evaluator> Greedy<E> {
+    pub fn new(evaluator: E) -> Self {
+        Self { evaluator }
+    }
+}
+
+impl<E: Evaluator> AI for Greedy<E> {
+    fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn {
+        let mut turns = board::get_valid_moves(board, player, enemy);
+        if player.walls > 0 {
+            turns.extend(
+                board::get_valid_walls(board, player, enemy)
+                    .into_iter()
+                    .map(Turn::Wall),
+            );
+        }
+
+        let mut best = (f64::NEG_INFINITY, turns[0]);
+        for turn in turns {
+            let score = match turn {
+                Turn::Move((x, y)) => {
+                    let player = Player { x, y, ..*player };
+                    if player.y == player.end_y {
+                        return turn;
+                    }
+
+                    self.evaluator.evaluate(board, &player, enemy)
+                }
+                Turn::Wall(wall) => {
+                    let mut board = board.clone();
+                    board::place_wall(&mut board, wall);
+                    let player = Player {
+                        walls: player.walls - 1,
+                        ..*player
+                    };
+
+                    self.evaluator.evaluate(&board, &player, enemy)
+                }
+            };
+
+            if score > best.0 {
+                best = (score, turn);
+            }
+        }
+
+        best.1
+    }
+}
@@ -0,0 +1,66 @@
+use super::super::quoridor::{
+    board::{self, Board},
+    game::Game,
+    player::Player,
+    run::{Turn, AI},
+};
+
+use std::io::{self, BufRead, BufReader, Stdin, Stdout, Write};
+
+/// A person typing turns in the usual notation (`e2`, `e3h`), asked again until the turn is
+/// legal. At the end of the input they give up by staying where they are, which forfeits.
+pub struct Human<R: BufRead = BufReader<Stdin>, W: Write = Stdout> {
+    input: R,
+    output: W,
+}
+
+impl Default for Human {
+    fn default() -> Self {
+        Self::new(BufReader::new(io::stdin()), io::stdout())
+    }
+}
+
+impl<R: BufRead, W: Write> Human<R, W> {
+    pub fn new(input: R, output: W) -> Self {
+        Self { input, output }
+    }
+}
+
+impl<R: BufRead, W: Write> AI for Human<R, W> {
+    fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn {
+        let game = Game::from_players(board, player, enemy);
+        let name = if player.end_y == board::get_board_height() - 1 {
+            "x"
+        } else {
+            "o"
+        };
+
+        loop {
+            let _ = write!(
+                self.output,
+                "Player {} ({} walls left), your turn: ",
+                name, player.walls
+            );
+            let _ = self.output.flush();
+
+            let mut line = String::new();
+            if self.input.read_line(&mut line).unwrap_or(0) == 0 {
+                return Turn::Move((player.x, player.y));
+            }
+
+            match line.trim().parse() {
+                Ok(turn) if game.is_legal(turn) => return turn,
+                Ok(turn) => {
+                    let _ = writeln!(self.output, "{} isn't legal here", turn);
+                }
+                Err(error) => {
+                    let _ = writeln!(
+                        self.output,
+                        "{}, type a move like e2 or a wall like e3h",
+                        error
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,407 @@
+use super::{
+    super::quoridor::{
+        board::{self, Board},
+        game::Game,
+        player::Player,
+        run::{Turn, AI},
+    },
+    pruning::WallPruning,
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+/// How `Mcts` uses more than one thread.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parallelism {
+    /// Every thread grows its own tree, the visits of the root turns are added up in the end.
+    Root,
+    /// All threads grow one shared tree. A thread adds a virtual loss to every node it passes
+    /// until its playout is done, so the other threads try different branches meanwhile.
+    Tree,
+}
+
+/// What the search found out about a turn of the root position.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TurnStats {
+    pub turn: Turn,
+    pub visits: u32,
+    /// Share of the playouts through this turn that were won.
+    pub win_rate: f64,
+}
+
+/// Monte Carlo tree search with UCT.
+///
+/// Playouts move along the shortest path and now and then place a random wall. After
+/// `playout_depth` turns the player with the shorter path (counting whose turn it is) is taken as
+/// the winner. A single thread with a seeded random number generator always plays the same turns.
+pub struct Mcts {
+    /// Playouts per turn, shared by all threads.
+    pub playouts: usize,
+    pub threads: usize,
+    pub parallelism: Parallelism,
+    /// Exploration constant of UCT.
+    pub exploration: f64,
+    /// Which walls the tree considers.
+    pub pruning: WallPruning,
+    pub playout_depth: usize,
+    /// Chance of trying to place a wall instead of moving in a playout.
+    pub wall_chance: f64,
+    rng: StdRng,
+}
+
+impl Default for Mcts {
+    fn default() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+}
+
+impl Mcts {
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(rng: StdRng) -> Self {
+        Self {
+            playouts: 2000,
+            threads: 1,
+            parallelism: Parallelism::Root,
+            exploration: 1.4,
+            pruning: WallPruning::default(),
+            playout_depth: 60,
+            wall_chance: 0.1,
+            rng,
+        }
+    }
+
+    /// Statistics of every root turn, the most visited first.
+    pub fn search(&mut self, game: &Game) -> Vec<TurnStats> {
+        let threads = self.threads.max(1);
+        let seeds: Vec<u64> = (0..threads).map(|_| self.rng.gen()).collect();
+        let this = &*self;
+
+        let mut stats = match self.parallelism {
+            _ if threads == 1 => this.grow(Tree::new(this, game), game, this.playouts, seeds[0]),
+            Parallelism::Root => {
+                let trees: Vec<Vec<TurnStats>> = thread::scope(|scope| {
+                    let handles: Vec<_> = seeds
+                        .iter()
+                        .enumerate()
+                        .map(|(index, &seed)| {
+                            // Spread the playouts as evenly as possible
+                            let playouts = (this.playouts + threads - 1 - index) / threads;
+                            scope.spawn(move || {
+                                this.grow(Tree::new(this, game), game, playouts, seed)
+                            })
+                        })
+                        .collect();
+
+                    handles
+                        .into_iter()
+                        .map(|handle| handle.join().unwrap())
+                        .collect()
+                });
+
+                merge(trees)
+            }
+            Parallelism::Tree => this.grow_shared(game, &seeds),
+        };
+
+        stats.sort_by_key(|stats| std::cmp::Reverse(stats.visits));
+        stats
+    }
+
+    /// Single threaded search on a tree of its own.
+    fn grow(&self, mut tree: Tree, game: &Game, playouts: usize, seed: u64) -> Vec<TurnStats> {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        for _ in 0..playouts {
+            let (mut path, leaf, expanded) = tree.select(game, self.exploration, &mut rng);
+            if let Some(turn) = expanded {
+                tree.expand(&mut path, turn, &leaf, self.candidates(&leaf));
+            }
+            let winner = self.playout(leaf, &mut rng);
+            tree.backpropagate(&path, winner, false);
+        }
+
+        tree.root_stats()
+    }
+
+    /// Tree parallelism: every thread works on the same tree behind a lock, which is only held
+    /// while selecting, expanding and backpropagating, not while listing the turns of a new node
+    /// or during the playout.
+    fn grow_shared(&self, game: &Game, seeds: &[u64]) -> Vec<TurnStats> {
+        let tree = Mutex::new(Tree::new(self, game));
+        let started = AtomicUsize::new(0);
+
+        thread::scope(|scope| {
+            for &seed in seeds {
+                let tree = &tree;
+                let started = &started;
+                scope.spawn(move || {
+                    let mut rng = StdRng::seed_from_u64(seed);
+
+                    while started.fetch_add(1, Ordering::Relaxed) < self.playouts {
+                        let (mut path, leaf, expanded) = {
+                            let mut tree = tree.lock().unwrap();
+                            let selected = tree.select(game, self.exploration, &mut rng);
+                            tree.add_virtual_loss(&selected.0);
+                            selected
+                        };
+
+                        if let Some(turn) = expanded {
+                            let untried = self.candidates(&leaf);
+                            let mut tree = tree.lock().unwrap();
+                            tree.expand(&mut path, turn, &leaf, untried);
+                            tree.add_virtual_loss(&path[path.len() - 1..]);
+                        }
+
+                        let winner = self.playout(leaf, &mut rng);
+                        tree.lock().unwrap().backpropagate(&path, winner, true);
+                    }
+                });
+            }
+        });
+
+        tree.into_inner().unwrap().root_stats()
+    }
+
+    /// Plays `game` out and returns whether player one won.
+    fn playout(&self, mut game: Game, rng: &mut StdRng) -> bool {
+        for _ in 0..self.playout_depth {
+            if let Some(winner) = game.winner() {
+                return winner;
+            }
+
+            let (player, enemy) = game.players();
+            let mut turn = None;
+
+            if player.walls > 0 && rng.gen_bool(self.wall_chance) {
+                // Random anchors are much cheaper than listing every valid wall
+                for _ in 0..4 {
+                    let wall = (
+                        rng.gen(),
+                        rng.gen_range(0..board::get_board_width() - 1),
+                        rng.gen_range(0..board::get_board_height() - 1),
+                    );
+                    if board::can_place_wall(&game.board, player, enemy, wall) {
+                        turn = Some(Turn::Wall(wall));
+                        break;
+                    }
+                }
+            }
+
+            let turn = turn.unwrap_or_else(|| {
+                board::get_next_move(&game.board, player, enemy)
+                    .expect("a player always has a path")
+            });
+            game.apply(turn);
+        }
+
+        if let Some(winner) = game.winner() {
+            return winner;
+        }
+
+        // Shortest path race, the player to move wins ties
+        let (player, enemy) = game.players();
+        let ahead = board::get_path_len(&game.board, player, enemy)
+            <= board::get_path_len(&game.board, enemy, player);
+
+        ahead == game.player_one_turn
+    }
+
+    fn candidates(&self, game: &Game) -> Vec<Turn> {
+        if game.winner().is_some() {
+            return Vec::new();
+        }
+
+        let (player, enemy) = game.players();
+        let mut turns = board::get_valid_moves(&game.board, player, enemy);
+        if player.walls > 0 {
+            turns.extend(
+                self.pruning
+                    .get_walls(&game.board, player, enemy)
+                    .into_iter()
+                    .map(Turn::Wall),
+            );
+        }
+
+        turns
+    }
+}
+
+struct Node {
+    turn: Option<Turn>,
+    /// Whether player one played `turn`.
+    player_one: bool,
+    children: Vec<usize>,
+    untried: Vec<Turn>,
+    visits: u32,
+    /// Won playouts, for the player who played `turn`.
+    wins: f64,
+    virtual_loss: u32,
+}
+
+struct Tree {
+    nodes: Vec<Node>,
+}
+
+impl Tree {
+    fn new(mcts: &Mcts, game: &Game) -> Self {
+        Self {
+            nodes: vec![Node {
+                turn: None,
+                player_one: !game.player_one_turn,
+                children: Vec::new(),
+                untried: mcts.candidates(game),
+                visits: 0,
+                wins: 0.0,
+                virtual_loss: 0,
+            }],
+        }
+    }
+
+    /// Walks down the tree by UCT and picks one untried turn to expand, if there is one. Returns
+    /// the indices of the nodes on the way, the position at the end (after the picked turn) and
+    /// the picked turn, which `expand` adds to the tree.
+    fn select(
+        &mut self,
+        game: &Game,
+        exploration: f64,
+        rng: &mut StdRng,
+    ) -> (Vec<usize>, Game, Option<Turn>) {
+        let mut game = game.clone();
+        let mut path = vec![0];
+        let mut current = 0;
+
+        while self.nodes[current].untried.is_empty() && !self.nodes[current].children.is_empty() {
+            current = self.best_child(current, exploration);
+            game.apply(self.nodes[current].turn.unwrap());
+            path.push(current);
+        }
+
+        let untried = &mut self.nodes[current].untried;
+        if untried.is_empty() {
+            return (path, game, None);
+        }
+
+        let index = rng.gen_range(0..untried.len());
+        let turn = untried.swap_remove(index);
+        game.apply(turn);
+
+        (path, game, Some(turn))
+    }
+
+    /// Adds `turn`, leading to `game`, as a child of the last node of `path` and appends it.
+    fn expand(&mut self, path: &mut Vec<usize>, turn: Turn, game: &Game, untried: Vec<Turn>) {
+        let parent = *path.last().unwrap();
+        let child = self.nodes.len();
+        self.nodes.push(Node {
+            turn: Some(turn),
+            player_one: !game.player_one_turn,
+            children: Vec::new(),
+            untried,
+            visits: 0,
+            wins: 0.0,
+            virtual_loss: 0,
+        });
+        self.nodes[parent].children.push(child);
+        path.push(child);
+    }
+
+    fn best_child(&self, parent: usize, exploration: f64) -> usize {
+        let parent = &self.nodes[parent];
+        let parent_visits = (parent.visits + parent.virtual_loss).max(1) as f64;
+
+        let uct = |index: &usize| {
+            let child = &self.nodes[*index];
+            // Virtual losses count as visits without a win
+            let visits = (child.visits + child.virtual_loss) as f64;
+            if visits == 0.0 {
+                return f64::INFINITY;
+            }
+
+            child.wins / visits + exploration * (parent_visits.ln() / visits).sqrt()
+        };
+
+        *parent
+            .children
+            .iter()
+            .max_by(|a, b| uct(a).total_cmp(&uct(b)))
+            .unwrap()
+    }
+
+    fn add_virtual_loss(&mut self, path: &[usize]) {
+        for &index in path {
+            self.nodes[index].virtual_loss += 1;
+        }
+    }
+
+    fn backpropagate(&mut self, path: &[usize], winner: bool, virtual_loss: bool) {
+        for &index in path {
+            let node = &mut self.nodes[index];
+            node.visits += 1;
+            if node.player_one == winner {
+                node.wins += 1.0;
+            }
+            if virtual_loss {
+                node.virtual_loss -= 1;
+            }
+        }
+    }
+
+    fn root_stats(&self) -> Vec<TurnStats> {
+        self.nodes[0]
+            .children
+            .iter()
+            .map(|&index| {
+                let node = &self.nodes[index];
+                TurnStats {
+                    turn: node.turn.unwrap(),
+                    visits: node.visits,
+                    win_rate: node.wins / node.visits.max(1) as f64,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Adds up the statistics of the same root turns of different trees.
+fn merge(trees: Vec<Vec<TurnStats>>) -> Vec<TurnStats> {
+    let mut merged: Vec<TurnStats> = Vec::new();
+
+    for stats in trees.into_iter().flatten() {
+        match merged.iter_mut().find(|merged| merged.turn == stats.turn) {
+            Some(merged) => {
+                let visits = merged.visits + stats.visits;
+                if visits > 0 {
+                    merged.win_rate = (merged.win_rate * merged.visits as f64
+                        + stats.win_rate * stats.visits as f64)
+                        / visits as f64;
+                }
+                merged.visits = visits;
+            }
+            None => merged.push(stats),
+        }
+    }
+
+    merged
+}
+
+impl AI for Mcts {
+    fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn {
+        match self
+            .search(&Game::from_players(board, player, enemy))
+            .first()
+        {
+            Some(stats) => stats.turn,
+            None => board::get_next_move(board, player, enemy).unwrap(),
+        }
+    }
+}
@@ -1,3 +1,15 @@
+pub mod analysis;
+pub mod annotate;
+pub mod book;
+pub mod eval;
+pub mod greedy;
+pub mod human;
+pub mod mcts;
 pub mod moving;
+pub mod pruning;
 pub mod random;
+pub mod registry;
+pub mod search;
+pub mod tt;
+pub mod tune;
 pub mod wall;
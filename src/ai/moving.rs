@@ -9,10 +9,6 @@ pub struct MoveOnly {}
 
 impl AI for MoveOnly {
     fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn {
-        let mut path = board::get_path_to_goal(board, player, enemy);
-        let _current = path.pop_front().unwrap();
-        let next = path.pop_front().unwrap();
-
-        Turn::Move(next)
+        board::get_next_move(board, player, enemy).unwrap()
     }
 }
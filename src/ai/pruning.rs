@@ -0,0 +1,182 @@
+use super::super::quoridor::{
+    board::{self, Board},
+    game::Game,
+    player::Player,
+    run::WallData,
+};
+
+use std::fmt;
+
+/// Which walls a search considers. A wall is considered if any enabled option allows it, with
+/// every option disabled (`WallPruning::OFF`) all valid walls are considered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WallPruning {
+    /// Walls blocking a step of either player's shortest path.
+    pub paths: bool,
+    /// Walls touching a wall already on the board.
+    pub adjacent: bool,
+    /// Walls along a tile at most this many steps (ignoring walls) from either pawn.
+    pub near_pawns: Option<usize>,
+}
+
+impl Default for WallPruning {
+    fn default() -> Self {
+        Self {
+            paths: true,
+            adjacent: false,
+            near_pawns: Some(1),
+        }
+    }
+}
+
+/// How much a `WallPruning` narrows `board::get_valid_walls`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PruningStats {
+    pub positions: usize,
+    /// Valid walls in all positions.
+    pub valid: usize,
+    /// Valid walls kept by the pruning.
+    pub kept: usize,
+}
+
+impl PruningStats {
+    /// Share of the valid walls that is kept.
+    pub fn ratio(&self) -> f64 {
+        if self.valid == 0 {
+            1.0
+        } else {
+            self.kept as f64 / self.valid as f64
+        }
+    }
+}
+
+impl fmt::Display for PruningStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} walls kept ({:.1}%) in {} positions",
+            self.kept,
+            self.valid,
+            self.ratio() * 100.0,
+            self.positions
+        )
+    }
+}
+
+impl WallPruning {
+    /// Considers every valid wall.
+    pub const OFF: WallPruning = WallPruning {
+        paths: false,
+        adjacent: false,
+        near_pawns: None,
+    };
+
+    pub fn is_off(&self) -> bool {
+        *self == Self::OFF
+    }
+
+    /// The valid walls this policy considers, in the order of `board::get_valid_walls`.
+    pub fn get_walls(&self, board: &Board, player: &Player, enemy: &Player) -> Vec<WallData> {
+        if self.is_off() {
+            return board::get_valid_walls(board, player, enemy);
+        }
+
+        let paths = if self.paths {
+            let mut edges = path_edges(board, player, enemy);
+            edges.extend(path_edges(board, enemy, player));
+            edges
+        } else {
+            Vec::new()
+        };
+        let placed = if self.adjacent {
+            board::get_walls(board)
+        } else {
+            Vec::new()
+        };
+
+        let mut walls = Vec::new();
+        for y in 0..board::get_board_height() - 1 {
+            for x in 0..board::get_board_width() - 1 {
+                for wall in [(true, x, y), (false, x, y)] {
+                    let considered = (self.paths && blocks_path(wall, &paths))
+                        || (self.adjacent && placed.iter().any(|&other| touches(wall, other)))
+                        || self
+                            .near_pawns
+                            .is_some_and(|k| near(wall, player, k) || near(wall, enemy, k));
+
+                    // Checking the geometry first saves the path searches of `can_place_wall`
+                    if considered && board::can_place_wall(board, player, enemy, wall) {
+                        walls.push(wall);
+                    }
+                }
+            }
+        }
+
+        walls
+    }
+
+    /// Compares this policy with `board::get_valid_walls` for the player to move in every game.
+    pub fn stats<'a>(&self, games: impl IntoIterator<Item = &'a Game>) -> PruningStats {
+        let mut stats = PruningStats::default();
+
+        for game in games {
+            let (player, enemy) = game.players();
+            stats.positions += 1;
+            stats.valid += board::get_valid_walls(&game.board, player, enemy).len();
+            stats.kept += self.get_walls(&game.board, player, enemy).len();
+        }
+
+        stats
+    }
+}
+
+/// Edges between neighbouring tiles along the shortest path of `player`, as pairs of indices.
+pub(crate) fn path_edges(board: &Board, player: &Player, enemy: &Player) -> Vec<(usize, usize)> {
+    let path: Vec<usize> = board::get_path_to_goal(board, player, enemy)
+        .into_iter()
+        .map(|(x, y)| board::point_to_index(x, y))
+        .collect();
+
+    path.windows(2)
+        .map(|step| (step[0].min(step[1]), step[0].max(step[1])))
+        .collect()
+}
+
+/// Whether `wall` blocks any of `edges`, as returned by `path_edges`.
+pub(crate) fn blocks_path((vert, x, y): WallData, edges: &[(usize, usize)]) -> bool {
+    let index = board::point_to_index;
+    let blocked = if vert {
+        [
+            (index(x, y), index(x + 1, y)),
+            (index(x, y + 1), index(x + 1, y + 1)),
+        ]
+    } else {
+        [
+            (index(x, y), index(x, y + 1)),
+            (index(x + 1, y), index(x + 1, y + 1)),
+        ]
+    };
+
+    blocked.iter().any(|edge| edges.contains(edge))
+}
+
+/// The three corners of the grid between tiles a wall runs through.
+fn corners((vert, x, y): WallData) -> [(usize, usize); 3] {
+    if vert {
+        [(x + 1, y), (x + 1, y + 1), (x + 1, y + 2)]
+    } else {
+        [(x, y + 1), (x + 1, y + 1), (x + 2, y + 1)]
+    }
+}
+
+fn touches(wall: WallData, other: WallData) -> bool {
+    let other = corners(other);
+    corners(wall).iter().any(|corner| other.contains(corner))
+}
+
+/// Whether one of the four tiles around the centre of `wall` is at most `k` steps from `player`.
+fn near((_, x, y): WallData, player: &Player, k: usize) -> bool {
+    [(x, y), (x + 1, y), (x, y + 1), (x + 1, y + 1)]
+        .iter()
+        .any(|&(tx, ty)| tx.abs_diff(player.x) + ty.abs_diff(player.y) <= k)
+}
@@ -4,21 +4,34 @@ use super::super::quoridor::{
     run::{Turn, AI},
 };
 
-use rand::{rngs::ThreadRng, Rng};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
-pub struct Random {
-    rng: ThreadRng,
+pub struct Random<R: Rng = StdRng> {
+    rng: R,
 }
 
 impl Default for Random {
     fn default() -> Self {
         Self {
-            rng: rand::thread_rng(),
+            rng: StdRng::from_entropy(),
         }
     }
 }
 
-impl AI for Random {
+impl Random<StdRng> {
+    /// Plays the same game every time for the same seed (and the same opponent).
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl<R: Rng> Random<R> {
+    pub fn with_rng(rng: R) -> Self {
+        Self { rng }
+    }
+}
+
+impl<R: Rng> AI for Random<R> {
     fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn {
         let mut moves = board::get_valid_moves(board, player, enemy);
 
@@ -36,19 +49,32 @@ impl AI for Random {
     }
 }
 
-pub struct RandomMoving {
-    rng: ThreadRng,
+pub struct RandomMoving<R: Rng = StdRng> {
+    rng: R,
 }
 
 impl Default for RandomMoving {
     fn default() -> Self {
         Self {
-            rng: rand::thread_rng(),
+            rng: StdRng::from_entropy(),
         }
     }
 }
 
-impl AI for RandomMoving {
+impl RandomMoving<StdRng> {
+    /// Plays the same game every time for the same seed (and the same opponent).
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl<R: Rng> RandomMoving<R> {
+    pub fn with_rng(rng: R) -> Self {
+        Self { rng }
+    }
+}
+
+impl<R: Rng> AI for RandomMoving<R> {
     fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn {
         let moves = board::get_valid_moves(board, player, enemy);
 
@@ -0,0 +1,182 @@
+//! AIs by name, for picking them in config files and on the command line: `mcts` plays with its
+//! defaults, `mcts:playouts=5000,threads=2` with parameters.
+
+use super::{
+    super::quoridor::run::AI,
+    eval::WeightedEval,
+    greedy::Greedy,
+    mcts::Mcts,
+    moving::MoveOnly,
+    random::{Random, RandomMoving},
+    search::Search,
+    wall::{WallFirstMax, WallFirstMinmax},
+};
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap, HashSet},
+    error::Error,
+    fmt,
+    str::FromStr,
+    time::Duration,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegistryError(pub String);
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for RegistryError {}
+
+/// The `key=value` parameters of an AI. Factories read what they understand, anything left
+/// unread is reported as unknown.
+pub struct Params {
+    seed: u64,
+    values: HashMap<String, String>,
+    read: RefCell<HashSet<String>>,
+}
+
+impl Params {
+    /// The value of `key`, which has to be lowercase, `default` if it isn't given.
+    pub fn get<T: FromStr>(&self, key: &str, default: T) -> Result<T, RegistryError> {
+        self.read.borrow_mut().insert(key.to_string());
+
+        match self.values.get(key) {
+            Some(value) => value
+                .parse()
+                .map_err(|_| RegistryError(format!("invalid value `{}` for `{}`", value, key))),
+            None => Ok(default),
+        }
+    }
+
+    /// The `seed` parameter, or the seed `Registry::create` was given.
+    pub fn seed(&self) -> Result<u64, RegistryError> {
+        self.get("seed", self.seed)
+    }
+}
+
+pub type Factory = Box<dyn Fn(&Params) -> Result<Box<dyn AI + Send>, RegistryError> + Send + Sync>;
+
+/// Factories by name. `Registry::default()` knows every shipped AI.
+pub struct Registry {
+    factories: BTreeMap<String, Factory>,
+}
+
+impl Registry {
+    /// A registry without any AIs.
+    pub fn empty() -> Self {
+        Self {
+            factories: BTreeMap::new(),
+        }
+    }
+
+    /// Registers `factory` as `name`, replacing what was registered before. Names are case
+    /// insensitive.
+    pub fn register(
+        &mut self,
+        name: &str,
+        factory: impl Fn(&Params) -> Result<Box<dyn AI + Send>, RegistryError> + Send + Sync + 'static,
+    ) {
+        self.factories
+            .insert(name.to_ascii_lowercase(), Box::new(factory));
+    }
+
+    /// Every registered name, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+
+    /// Creates the AI described by `spec`, a name optionally followed by a colon and
+    /// comma-separated `key=value` parameters. Keys are case insensitive like names and can only
+    /// be given once. AIs playing randomly use `seed` unless a `seed` parameter is given.
+    pub fn create(&self, spec: &str, seed: u64) -> Result<Box<dyn AI + Send>, RegistryError> {
+        let (name, params) = spec.split_once(':').unwrap_or((spec, ""));
+        let factory = self
+            .factories
+            .get(&name.to_ascii_lowercase())
+            .ok_or_else(|| RegistryError(format!("unknown AI `{}`", name)))?;
+
+        let mut values = HashMap::new();
+        for param in params.split(',').filter(|param| !param.is_empty()) {
+            let (key, value) = param.split_once('=').ok_or_else(|| {
+                RegistryError(format!(
+                    "expected a parameter like `key=value`, got `{}`",
+                    param
+                ))
+            })?;
+            let key = key.to_ascii_lowercase();
+            if values.contains_key(&key) {
+                return Err(RegistryError(format!("`{}` is given twice", key)));
+            }
+            values.insert(key, value.to_string());
+        }
+
+        let params = Params {
+            seed,
+            values,
+            read: RefCell::new(HashSet::new()),
+        };
+        let ai = factory(&params)?;
+
+        let read = params.read.borrow();
+        let mut unknown: Vec<&String> = params
+            .values
+            .keys()
+            .filter(|key| !read.contains(*key))
+            .collect();
+        unknown.sort();
+        match unknown.first() {
+            Some(key) => Err(RegistryError(format!(
+                "`{}` has no parameter `{}`",
+                name, key
+            ))),
+            None => Ok(ai),
+        }
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+
+        registry.register("moveonly", |_| Ok(Box::new(MoveOnly::default())));
+        registry.register("random", |params| {
+            Ok(Box::new(Random::with_seed(params.seed()?)))
+        });
+        registry.register("randommoving", |params| {
+            Ok(Box::new(RandomMoving::with_seed(params.seed()?)))
+        });
+        registry.register("wallfirstmax", |params| {
+            Ok(Box::new(WallFirstMax::with_seed(params.seed()?)))
+        });
+        registry.register("wallfirstminmax", |params| {
+            Ok(Box::new(WallFirstMinmax::with_seed(params.seed()?)))
+        });
+        registry.register("greedy", |_| {
+            Ok(Box::new(Greedy::new(WeightedEval::default())))
+        });
+        registry.register("search", |params| {
+            let mut search = Search::new(
+                WeightedEval::default(),
+                Duration::from_millis(params.get("ms", 1000)?),
+            );
+            search.max_depth = params.get("depth", search.max_depth)?;
+            search.threads = params.get("threads", search.threads)?;
+            search.ponder = params.get("ponder", search.ponder)?;
+            Ok(Box::new(search))
+        });
+        registry.register("mcts", |params| {
+            let mut mcts = Mcts::with_seed(params.seed()?);
+            mcts.playouts = params.get("playouts", mcts.playouts)?;
+            mcts.threads = params.get("threads", mcts.threads)?;
+            mcts.exploration = params.get("exploration", mcts.exploration)?;
+            Ok(Box::new(mcts))
+        });
+
+        registry
+    }
+}
@@ -0,0 +1,575 @@
+use super::{
+    super::quoridor::{
+        board::{self, Board},
+        distance::distance_map,
+        game::Game,
+        player::Player,
+        race::{self, Outcome, RaceTable},
+        run::{Context, Turn, AI},
+    },
+    eval::{Evaluator, WeightedEval},
+    pruning::{blocks_path, path_edges, WallPruning},
+    tt::{Bound, Entry, TranspositionTable},
+};
+
+use std::{
+    collections::HashMap,
+    fmt, mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// Score of a won position. Wins closer to the root score higher, so a won position found after
+/// `ply` turns scores `WIN - ply`.
+pub const WIN: f64 = 1_000_000.0;
+
+/// Scores this close to `WIN` are wins, stored relative to the position in the table.
+pub const WIN_THRESHOLD: f64 = WIN - 1000.0;
+
+/// Race tables by `board::hash`, shared by all threads.
+type RaceCache = Mutex<HashMap<u64, Arc<RaceTable>>>;
+
+/// How often (in nodes) the clock is checked.
+const CHECK_INTERVAL: u64 = 1024;
+
+/// What an iteration of `Search` found.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchInfo {
+    pub depth: usize,
+    pub nodes: u64,
+    /// From the point of view of the player to move.
+    pub score: f64,
+    /// Principal variation: the best turn followed by the best replies found.
+    pub pv: Vec<Turn>,
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for SearchInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "depth {} nodes {} score {:.2} time {}ms pv",
+            self.depth,
+            self.nodes,
+            self.score,
+            self.elapsed.as_millis()
+        )?;
+        for turn in &self.pv {
+            write!(f, " {}", turn)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Alpha-beta search with iterative deepening: searches one turn deeper at a time until
+/// `time_limit` runs out and plays the best turn of the last finished iteration.
+///
+/// Only the walls allowed by `pruning` are searched. Turns are tried in this order: the best turn
+/// stored in the transposition table, pawn moves along the shortest path, killer turns, walls
+/// blocking the enemy's shortest path and everything else by history score.
+///
+/// With more than one thread the search runs as Lazy SMP: helper threads search the same position
+/// (every other one a turn deeper) and only share what they find through the transposition
+/// table. A single thread searches deterministically, so with a depth limit instead of a time
+/// limit the same positions always lead to the same turns.
+///
+/// With `solve_races` set, positions where neither player has walls left are solved exactly by
+/// `race::RaceTable` instead of being searched or evaluated.
+///
+/// With `ponder` set, it keeps searching in the background while the enemy thinks, assuming the
+/// enemy plays the second turn of the principal variation. If they do, the next search finds the
+/// results in the transposition table.
+pub struct Search<E: Evaluator = WeightedEval> {
+    pub evaluator: E,
+    pub time_limit: Duration,
+    pub max_depth: usize,
+    /// Which walls are searched.
+    pub pruning: WallPruning,
+    pub threads: usize,
+    pub solve_races: bool,
+    pub ponder: bool,
+    /// Prints every finished iteration to stderr.
+    pub verbose: bool,
+    info: Option<SearchInfo>,
+    table: Arc<TranspositionTable>,
+    races: Arc<RaceCache>,
+    history: HashMap<Turn, u64>,
+    pondering: Option<Ponder>,
+}
+
+/// A search running in the background, stopped when dropped.
+struct Ponder {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for Ponder {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl<E: Evaluator + Sync + Default> Default for Search<E> {
+    fn default() -> Self {
+        Self::new(E::default(), Duration::from_secs(1))
+    }
+}
+
+impl<E: Evaluator + Sync> Search<E> {
+    pub fn new(evaluator: E, time_limit: Duration) -> Self {
+        Self {
+            evaluator,
+            time_limit,
+            max_depth: 64,
+            pruning: WallPruning::default(),
+            threads: 1,
+            solve_races: true,
+            ponder: false,
+            verbose: false,
+            info: None,
+            table: Arc::new(TranspositionTable::new(18)),
+            races: Arc::default(),
+            history: HashMap::new(),
+            pondering: None,
+        }
+    }
+
+    /// The result of the last search.
+    pub fn info(&self) -> Option<&SearchInfo> {
+        self.info.as_ref()
+    }
+
+    /// Replaces the transposition table with an empty one of `2^bits` entries.
+    pub fn resize_table(&mut self, bits: u32) {
+        self.pondering = None;
+        self.table = Arc::new(TranspositionTable::new(bits));
+    }
+
+    /// Searches `game` until the time runs out, `max_depth` is reached or the game is decided.
+    /// The first iteration always finishes, however long it takes.
+    pub fn search(&mut self, game: &Game) -> SearchInfo {
+        // Whatever the background search found is in the table by now
+        self.pondering = None;
+
+        let start = Instant::now();
+        let stop = AtomicBool::new(false);
+
+        if self.solve_races && race::is_race(game) {
+            return self.solve_race(game, start);
+        }
+
+        let max_depth = self.max_depth.max(1);
+
+        // Keep what was learned from earlier searches, but let it fade
+        for score in self.history.values_mut() {
+            *score /= 2;
+        }
+
+        let history = mem::take(&mut self.history);
+        let races = self.solve_races.then_some(&*self.races);
+        let mut main = Worker::new(&self.evaluator, self.pruning, &self.table, races, &stop);
+        main.history = history;
+        let mut info = SearchInfo::default();
+
+        let helper_nodes: u64 = thread::scope(|scope| {
+            let helpers: Vec<_> = (1..self.threads)
+                .map(|id| {
+                    let mut helper =
+                        Worker::new(&self.evaluator, self.pruning, &self.table, races, &stop);
+                    scope.spawn(move || {
+                        for depth in (1 + id % 2)..=max_depth {
+                            helper.negamax(game, depth, 0, f64::NEG_INFINITY, f64::INFINITY);
+                            if helper.stopped {
+                                break;
+                            }
+                        }
+
+                        helper.nodes
+                    })
+                })
+                .collect();
+
+            for depth in 1..=max_depth {
+                let (score, pv) = main.negamax(game, depth, 0, f64::NEG_INFINITY, f64::INFINITY);
+                if main.stopped {
+                    break;
+                }
+
+                info = SearchInfo {
+                    depth,
+                    nodes: main.nodes,
+                    score,
+                    pv,
+                    elapsed: start.elapsed(),
+                };
+                if self.verbose {
+                    eprintln!("{}", info);
+                }
+
+                // The next iteration takes a lot longer than this one, don't start what can't
+                // finish
+                if score.abs() >= WIN_THRESHOLD || start.elapsed() * 2 > self.time_limit {
+                    break;
+                }
+                main.deadline = start.checked_add(self.time_limit);
+            }
+
+            stop.store(true, Ordering::Relaxed);
+            helpers
+                .into_iter()
+                .map(|helper| helper.join().unwrap())
+                .sum()
+        });
+
+        info.nodes = main.nodes + helper_nodes;
+        info.elapsed = start.elapsed();
+        let history = main.history;
+        self.history = history;
+        self.info = Some(info.clone());
+
+        info
+    }
+
+    fn solve_race(&mut self, game: &Game, start: Instant) -> SearchInfo {
+        let table = race_table(&self.races, game);
+        let outcome = table.outcome(game);
+        let pv = table.principal_variation(game, 2 * board::get_board_size());
+
+        let info = SearchInfo {
+            depth: pv.len(),
+            nodes: 1,
+            score: race_score(outcome, 0),
+            pv,
+            elapsed: start.elapsed(),
+        };
+        if self.verbose {
+            eprintln!("{}", info);
+        }
+        self.info = Some(info.clone());
+
+        info
+    }
+}
+
+fn race_table(races: &RaceCache, game: &Game) -> Arc<RaceTable> {
+    let hash = board::hash(&game.board);
+    if let Some(table) = races.lock().unwrap().get(&hash) {
+        return Arc::clone(table);
+    }
+
+    // Solved without holding the lock, another thread may solve the same board meanwhile
+    let table = Arc::new(RaceTable::new(&game.board));
+    let mut races = races.lock().unwrap();
+    if races.len() >= 256 {
+        races.clear();
+    }
+    races.insert(hash, Arc::clone(&table));
+
+    table
+}
+
+/// Scores an exact outcome like the search scores won positions.
+fn race_score(outcome: Outcome, ply: usize) -> f64 {
+    match outcome {
+        Outcome::Win(n) => WIN - (ply + n) as f64,
+        Outcome::Loss(n) => (ply + n) as f64 - WIN,
+        Outcome::Draw => 0.0,
+    }
+}
+
+/// The state of one search thread.
+struct Worker<'a, E> {
+    evaluator: &'a E,
+    pruning: WallPruning,
+    table: &'a TranspositionTable,
+    races: Option<&'a RaceCache>,
+    stop: &'a AtomicBool,
+    deadline: Option<Instant>,
+    killers: Vec<[Option<Turn>; 2]>,
+    history: HashMap<Turn, u64>,
+    nodes: u64,
+    stopped: bool,
+}
+
+impl<'a, E: Evaluator> Worker<'a, E> {
+    fn new(
+        evaluator: &'a E,
+        pruning: WallPruning,
+        table: &'a TranspositionTable,
+        races: Option<&'a RaceCache>,
+        stop: &'a AtomicBool,
+    ) -> Self {
+        Self {
+            evaluator,
+            pruning,
+            table,
+            races,
+            stop,
+            deadline: None,
+            killers: Vec::new(),
+            history: HashMap::new(),
+            nodes: 0,
+            stopped: false,
+        }
+    }
+
+    fn negamax(
+        &mut self,
+        game: &Game,
+        depth: usize,
+        ply: usize,
+        mut alpha: f64,
+        beta: f64,
+    ) -> (f64, Vec<Turn>) {
+        self.nodes += 1;
+        if self.stop.load(Ordering::Relaxed)
+            || (self.nodes.is_multiple_of(CHECK_INTERVAL)
+                && self
+                    .deadline
+                    .is_some_and(|deadline| Instant::now() >= deadline))
+        {
+            self.stopped = true;
+        }
+        if self.stopped {
+            return (0.0, Vec::new());
+        }
+
+        // The player who just moved reached their goal
+        if game.winner().is_some() {
+            return (ply as f64 - WIN, Vec::new());
+        }
+
+        if let Some(races) = self.races.filter(|_| race::is_race(game)) {
+            let outcome = race_table(races, game).outcome(game);
+            return (race_score(outcome, ply), Vec::new());
+        }
+
+        if depth == 0 {
+            let (player, enemy) = game.players();
+            return (
+                self.evaluator.evaluate(&game.board, player, enemy),
+                Vec::new(),
+            );
+        }
+
+        let hash = game.hash();
+        let entry = self.table.get(hash);
+        if let Some(entry) = entry {
+            // Never cut at the root, it has to come up with a turn
+            if ply > 0 && entry.depth >= depth {
+                let score = from_table(entry.score, ply);
+                let cutoff = match entry.bound {
+                    Bound::Exact => true,
+                    Bound::Lower => score >= beta,
+                    Bound::Upper => score <= alpha,
+                };
+
+                if cutoff {
+                    // The rest of the variation isn't stored, so the PV ends here
+                    return (score, entry.turn.into_iter().collect());
+                }
+            }
+        }
+
+        let original_alpha = alpha;
+        let mut best = (f64::NEG_INFINITY, Vec::new());
+
+        for turn in self.ordered_turns(game, entry.and_then(|entry| entry.turn), ply) {
+            let mut child = game.clone();
+            child.apply(turn);
+
+            let (score, mut pv) = self.negamax(&child, depth - 1, ply + 1, -beta, -alpha);
+            if self.stopped {
+                return (0.0, Vec::new());
+            }
+
+            let score = -score;
+            if score > best.0 {
+                pv.insert(0, turn);
+                best = (score, pv);
+            }
+            if score > alpha {
+                alpha = score;
+            }
+            if alpha >= beta {
+                self.store_killer(ply, turn);
+                *self.history.entry(turn).or_default() += (depth * depth) as u64;
+                break;
+            }
+        }
+
+        let bound = if best.0 <= original_alpha {
+            Bound::Upper
+        } else if best.0 >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.table.store(
+            hash,
+            Entry {
+                depth,
+                score: to_table(best.0, ply),
+                bound,
+                turn: best.1.first().copied(),
+            },
+        );
+
+        best
+    }
+
+    fn store_killer(&mut self, ply: usize, turn: Turn) {
+        if self.killers.len() <= ply {
+            self.killers.resize(ply + 1, [None; 2]);
+        }
+
+        let killers = &mut self.killers[ply];
+        if killers[0] != Some(turn) {
+            killers[1] = killers[0];
+            killers[0] = Some(turn);
+        }
+    }
+
+    fn ordered_turns(&self, game: &Game, best_turn: Option<Turn>, ply: usize) -> Vec<Turn> {
+        let (player, enemy) = game.players();
+        let killers = self.killers.get(ply).copied().unwrap_or_default();
+        let distances = distance_map(&game.board, player.end_y);
+        let current = distances.get(player.x, player.y);
+        let enemy_path = path_edges(&game.board, enemy, player);
+
+        let mut turns = board::get_valid_moves(&game.board, player, enemy);
+        if player.walls > 0 {
+            turns.extend(
+                self.pruning
+                    .get_walls(&game.board, player, enemy)
+                    .into_iter()
+                    .map(Turn::Wall),
+            );
+        }
+
+        let mut scored: Vec<(u64, Turn)> = turns
+            .into_iter()
+            .map(|turn| {
+                let score = if Some(turn) == best_turn {
+                    1 << 40
+                } else {
+                    let history = self.history.get(&turn).copied().unwrap_or(0);
+                    match turn {
+                        Turn::Move((x, y))
+                            if matches!((distances.get(x, y), current), (Some(to), Some(from)) if to < from) =>
+                        {
+                            1 << 32
+                        }
+                        _ if killers.contains(&Some(turn)) => 1 << 31,
+                        Turn::Wall(wall) if blocks_path(wall, &enemy_path) => (1 << 30) + history,
+                        _ => history,
+                    }
+                };
+
+                (score, turn)
+            })
+            .collect();
+        scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+
+        scored.into_iter().map(|(_, turn)| turn).collect()
+    }
+}
+
+/// Wins are stored as turns from the stored position rather than from the root.
+fn to_table(score: f64, ply: usize) -> f64 {
+    if score >= WIN_THRESHOLD {
+        score + ply as f64
+    } else if score <= -WIN_THRESHOLD {
+        score - ply as f64
+    } else {
+        score
+    }
+}
+
+fn from_table(score: f64, ply: usize) -> f64 {
+    if score >= WIN_THRESHOLD {
+        score - ply as f64
+    } else if score <= -WIN_THRESHOLD {
+        score + ply as f64
+    } else {
+        score
+    }
+}
+
+impl<E: Evaluator + Clone + Send + Sync + 'static> AI for Search<E> {
+    fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn {
+        match self
+            .search(&Game::from_players(board, player, enemy))
+            .pv
+            .first()
+        {
+            Some(&turn) => turn,
+            None => board::get_next_move(board, player, enemy).unwrap(),
+        }
+    }
+
+    /// With a clock, searches at most a twentieth of the time left. The first iteration always
+    /// finishes though, so a clock that is nearly out can still be overrun.
+    fn play_in_context(&mut self, context: &Context) -> Turn {
+        let (player, enemy) = context.players();
+        let Some(time_left) = context.time_left else {
+            return self.play(&context.game.board, player, enemy);
+        };
+
+        let time_limit = self.time_limit;
+        self.time_limit = time_limit.min(time_left / 20);
+        let turn = self.play(&context.game.board, player, enemy);
+        self.time_limit = time_limit;
+
+        turn
+    }
+
+    fn ponder_turn(&self) -> Option<Turn> {
+        if !self.ponder {
+            return None;
+        }
+
+        self.info.as_ref().and_then(|info| info.pv.get(1).copied())
+    }
+
+    fn ponder_start(&mut self, board: &Board, player: &Player, enemy: &Player, _predicted: Turn) {
+        let game = Game::from_players(board, player, enemy);
+        let evaluator = self.evaluator.clone();
+        let pruning = self.pruning;
+        let table = Arc::clone(&self.table);
+        let races = self.solve_races.then(|| Arc::clone(&self.races));
+        let max_depth = self.max_depth.max(1);
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let handle = thread::spawn({
+            let stop = Arc::clone(&stop);
+            move || {
+                let mut worker = Worker::new(&evaluator, pruning, &table, races.as_deref(), &stop);
+                for depth in 1..=max_depth {
+                    let (score, _) =
+                        worker.negamax(&game, depth, 0, f64::NEG_INFINITY, f64::INFINITY);
+                    if worker.stopped || score.abs() >= WIN_THRESHOLD {
+                        break;
+                    }
+                }
+            }
+        });
+
+        self.pondering = Some(Ponder {
+            stop,
+            handle: Some(handle),
+        });
+    }
+
+    fn ponder_miss(&mut self) {
+        self.pondering = None;
+    }
+}
@@ -0,0 +1,146 @@
+use super::super::quoridor::{
+    board,
+    run::{Turn, WallData},
+};
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// How a stored score relates to the real score of a position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bound {
+    Exact,
+    /// The real score is at least the stored one (the search failed high).
+    Lower,
+    /// The real score is at most the stored one (the search failed low).
+    Upper,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Entry {
+    pub depth: usize,
+    pub score: f64,
+    pub bound: Bound,
+    pub turn: Option<Turn>,
+}
+
+/// A fixed size hash table of search results that can be shared between threads without locks.
+///
+/// Every slot stores the entry and the position hash xor the entry, so an entry half written by
+/// another thread doesn't match its hash and is ignored.
+pub struct TranspositionTable {
+    slots: Vec<[AtomicU64; 2]>,
+}
+
+impl TranspositionTable {
+    /// A table with `2^bits` slots of 16 bytes.
+    pub fn new(bits: u32) -> Self {
+        Self {
+            slots: (0..1usize << bits)
+                .map(|_| [AtomicU64::new(0), AtomicU64::new(0)])
+                .collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn clear(&self) {
+        for slot in &self.slots {
+            slot[0].store(0, Ordering::Relaxed);
+            slot[1].store(0, Ordering::Relaxed);
+        }
+    }
+
+    pub fn get(&self, hash: u64) -> Option<Entry> {
+        let slot = &self.slots[hash as usize & (self.slots.len() - 1)];
+        let check = slot[0].load(Ordering::Relaxed);
+        let data = slot[1].load(Ordering::Relaxed);
+
+        if data == 0 || check ^ data != hash {
+            return None;
+        }
+
+        decode(data)
+    }
+
+    /// Stores `entry`, unless the slot holds a deeper search of the same position.
+    pub fn store(&self, hash: u64, entry: Entry) {
+        if let Some(old) = self.get(hash) {
+            if old.depth > entry.depth {
+                return;
+            }
+        }
+
+        let data = encode(entry);
+        let slot = &self.slots[hash as usize & (self.slots.len() - 1)];
+        slot[0].store(hash ^ data, Ordering::Relaxed);
+        slot[1].store(data, Ordering::Relaxed);
+    }
+}
+
+/// Packs an entry into 64 bits: the score as `f32`, then 8 bits of depth, 2 of bound and 16 of
+/// turn. A bound of 0 is never used, so 0 means an empty slot.
+fn encode(entry: Entry) -> u64 {
+    let bound = match entry.bound {
+        Bound::Exact => 1,
+        Bound::Lower => 2,
+        Bound::Upper => 3,
+    };
+
+    (entry.score as f32).to_bits() as u64
+        | (entry.depth.min(255) as u64) << 32
+        | bound << 40
+        | (encode_turn(entry.turn) as u64) << 42
+}
+
+fn decode(data: u64) -> Option<Entry> {
+    let bound = match (data >> 40) & 3 {
+        1 => Bound::Exact,
+        2 => Bound::Lower,
+        3 => Bound::Upper,
+        _ => return None,
+    };
+
+    Some(Entry {
+        depth: ((data >> 32) & 255) as usize,
+        score: f32::from_bits(data as u32) as f64,
+        bound,
+        turn: decode_turn((data >> 42) as u16),
+    })
+}
+
+/// 0 for no turn, then every tile and every wall. Codes past the last wall decode to no turn.
+pub(crate) fn encode_turn(turn: Option<Turn>) -> u16 {
+    let tiles = board::get_board_width() * board::get_board_height();
+
+    match turn {
+        None => 0,
+        Some(Turn::Move((x, y))) => 1 + board::point_to_index(x, y) as u16,
+        Some(Turn::Wall((vert, x, y))) => {
+            (1 + tiles + 2 * (y * (board::get_board_width() - 1) + x) + vert as usize) as u16
+        }
+    }
+}
+
+pub(crate) fn decode_turn(code: u16) -> Option<Turn> {
+    let tiles = board::get_board_width() * board::get_board_height();
+    let code = code as usize;
+
+    if code == 0 {
+        None
+    } else if code <= tiles {
+        Some(Turn::Move(board::index_to_point(code - 1)))
+    } else {
+        let wall = code - 1 - tiles;
+        let anchor = wall / 2;
+        let width = board::get_board_width() - 1;
+        let data: WallData = (wall % 2 == 1, anchor % width, anchor / width);
+
+        (data.2 < board::get_board_height() - 1).then_some(Turn::Wall(data))
+    }
+}
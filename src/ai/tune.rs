@@ -0,0 +1,337 @@
+use super::{
+    super::quoridor::{
+        board::{self, Board},
+        game::Game,
+        observer::GameObserver,
+        player::Player,
+        run::{run, run_with_observers, GameResult, Turn, AI},
+    },
+    eval::{Feature, WeightedEval},
+    greedy::Greedy,
+};
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use std::time::Duration;
+
+const FEATURES: usize = Feature::ALL.len();
+
+/// Greedy play with a chance of a random turn, so self-play games differ from each other. After
+/// `max_turns` turns it only follows its shortest path, which makes sure games end even with
+/// weights that don't want to.
+struct SelfPlay {
+    greedy: Greedy<WeightedEval>,
+    rng: StdRng,
+    epsilon: f64,
+    turns: usize,
+    max_turns: usize,
+}
+
+impl SelfPlay {
+    fn new(weights: WeightedEval, seed: u64, epsilon: f64) -> Self {
+        Self {
+            greedy: Greedy::new(weights),
+            rng: StdRng::seed_from_u64(seed),
+            epsilon,
+            turns: 0,
+            max_turns: 60,
+        }
+    }
+}
+
+impl AI for SelfPlay {
+    fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn {
+        self.turns += 1;
+
+        if self.turns > self.max_turns {
+            return board::get_next_move(board, player, enemy).unwrap();
+        }
+
+        if self.rng.gen_bool(self.epsilon) {
+            let mut turns = board::get_valid_moves(board, player, enemy);
+            if player.walls > 0 {
+                turns.extend(
+                    board::get_valid_walls(board, player, enemy)
+                        .into_iter()
+                        .map(Turn::Wall),
+                );
+            }
+
+            return *turns.choose(&mut self.rng).unwrap();
+        }
+
+        self.greedy.play(board, player, enemy)
+    }
+}
+
+/// A position seen in a self-play game: every feature from the point of view of the player to
+/// move and whether that player went on to win (1) or lose (0).
+#[derive(Clone, Copy, Debug)]
+pub struct Sample {
+    pub features: [f64; FEATURES],
+    pub result: f64,
+}
+
+#[derive(Default)]
+struct Collector {
+    positions: Vec<([f64; FEATURES], bool)>,
+    samples: Vec<Sample>,
+}
+
+impl Collector {
+    fn collect(&mut self, game: &Game) {
+        if game.winner().is_some() {
+            return;
+        }
+
+        let (player, enemy) = game.players();
+        self.positions.push((
+            Feature::ALL.map(|feature| feature.value(&game.board, player, enemy)),
+            game.player_one_turn,
+        ));
+    }
+}
+
+impl GameObserver for Collector {
+    fn game_start(&mut self, game: &Game) {
+        self.positions.clear();
+        self.collect(game);
+    }
+
+    fn turn_played(&mut self, game: &Game, _turn: Turn, _elapsed: Duration) {
+        self.collect(game);
+    }
+
+    fn game_end(&mut self, _game: &Game, result: &GameResult) {
+        self.samples.extend(
+            self.positions
+                .drain(..)
+                .map(|(features, player_one)| Sample {
+                    features,
+                    result: if player_one == result.winner {
+                        1.0
+                    } else {
+                        0.0
+                    },
+                }),
+        );
+    }
+}
+
+/// Plays `games` games of `weights` against itself, picking a random turn `epsilon` of the time,
+/// and returns every position along with its outcome.
+pub fn self_play(games: usize, seed: u64, weights: &WeightedEval, epsilon: f64) -> Vec<Sample> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut collector = Collector::default();
+
+    for _ in 0..games {
+        run_with_observers(
+            SelfPlay::new(weights.clone(), rng.gen(), epsilon),
+            SelfPlay::new(weights.clone(), rng.gen(), epsilon),
+            &mut [&mut collector],
+        );
+    }
+
+    collector.samples
+}
+
+fn to_vector(weights: &WeightedEval) -> [f64; FEATURES] {
+    Feature::ALL.map(|feature| weights.weight(feature))
+}
+
+fn from_vector(vector: [f64; FEATURES]) -> WeightedEval {
+    WeightedEval::new(Feature::ALL.into_iter().zip(vector).collect())
+}
+
+fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+pub struct TexelOptions {
+    /// Steepness of the sigmoid mapping an evaluation to a win probability.
+    pub scale: f64,
+    pub learning_rate: f64,
+    pub iterations: usize,
+}
+
+impl Default for TexelOptions {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            learning_rate: 0.05,
+            iterations: 1000,
+        }
+    }
+}
+
+/// Mean squared error between the outcomes and the win probabilities `weights` predict.
+pub fn texel_error(samples: &[Sample], weights: &WeightedEval, scale: f64) -> f64 {
+    let weights = to_vector(weights);
+
+    samples
+        .iter()
+        .map(|sample| {
+            let eval: f64 = weights
+                .iter()
+                .zip(&sample.features)
+                .map(|(w, f)| w * f)
+                .sum();
+            (sample.result - sigmoid(scale * eval)).powi(2)
+        })
+        .sum::<f64>()
+        / samples.len() as f64
+}
+
+/// Texel-style tuning: fits the weights with gradient descent so that `sigmoid(scale * eval)`
+/// predicts the outcome of the game a position came from.
+pub fn texel(samples: &[Sample], start: &WeightedEval, options: &TexelOptions) -> WeightedEval {
+    let mut weights = to_vector(start);
+
+    if samples.is_empty() {
+        return from_vector(weights);
+    }
+
+    for _ in 0..options.iterations {
+        let mut gradient = [0.0; FEATURES];
+
+        for sample in samples {
+            let eval: f64 = weights
+                .iter()
+                .zip(&sample.features)
+                .map(|(w, f)| w * f)
+                .sum();
+            let prediction = sigmoid(options.scale * eval);
+            let factor = -2.0
+                * (sample.result - prediction)
+                * prediction
+                * (1.0 - prediction)
+                * options.scale;
+
+            for (g, f) in gradient.iter_mut().zip(&sample.features) {
+                *g += factor * f;
+            }
+        }
+
+        for (w, g) in weights.iter_mut().zip(gradient) {
+            *w -= options.learning_rate * g / samples.len() as f64;
+        }
+    }
+
+    from_vector(weights)
+}
+
+pub struct GeneticOptions {
+    pub population: usize,
+    pub generations: usize,
+    /// Games against the reference weights per candidate, half of them as player one.
+    pub games: usize,
+    /// Standard deviation of the noise added to every weight of a child.
+    pub mutation: f64,
+    pub seed: u64,
+}
+
+impl Default for GeneticOptions {
+    fn default() -> Self {
+        Self {
+            population: 12,
+            generations: 10,
+            games: 8,
+            mutation: 0.2,
+            seed: 0,
+        }
+    }
+}
+
+/// Share of `games` won by `candidate` against `reference`, alternating who moves first.
+pub fn match_score(
+    candidate: &WeightedEval,
+    reference: &WeightedEval,
+    games: usize,
+    seed: u64,
+) -> f64 {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut wins = 0;
+
+    for game in 0..games {
+        let one = SelfPlay::new(candidate.clone(), rng.gen(), 0.05);
+        let two = SelfPlay::new(reference.clone(), rng.gen(), 0.05);
+
+        if game % 2 == 0 {
+            wins += run(one, two).winner as usize;
+        } else {
+            wins += !run(two, one).winner as usize;
+        }
+    }
+
+    wins as f64 / games as f64
+}
+
+/// Genetic search over match results: every generation is scored by playing against `start`,
+/// the better half survives and is recombined and mutated into the next generation.
+pub fn genetic(start: &WeightedEval, options: &GeneticOptions) -> WeightedEval {
+    let mut rng = StdRng::seed_from_u64(options.seed);
+    let start_vector = to_vector(start);
+    let population = options.population.max(2);
+
+    let mutate = |vector: [f64; FEATURES], rng: &mut StdRng| {
+        vector.map(|w| w + options.mutation * gaussian(rng))
+    };
+
+    let mut candidates: Vec<[f64; FEATURES]> = (0..population)
+        .map(|index| {
+            if index == 0 {
+                start_vector
+            } else {
+                mutate(start_vector, &mut rng)
+            }
+        })
+        .collect();
+    let mut best = (f64::NEG_INFINITY, start_vector);
+
+    for _ in 0..options.generations {
+        let seed = rng.gen();
+        let mut scored: Vec<(f64, [f64; FEATURES])> = candidates
+            .iter()
+            .map(|&candidate| {
+                let score = match_score(&from_vector(candidate), start, options.games, seed);
+                (score, candidate)
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        if scored[0].0 > best.0 {
+            best = scored[0];
+        }
+
+        let parents: Vec<[f64; FEATURES]> = scored
+            .iter()
+            .take(population / 2)
+            .map(|&(_, candidate)| candidate)
+            .collect();
+
+        candidates = parents.clone();
+        while candidates.len() < population {
+            let one = parents.choose(&mut rng).unwrap();
+            let two = parents.choose(&mut rng).unwrap();
+            let mut child = [0.0; FEATURES];
+            for (index, weight) in child.iter_mut().enumerate() {
+                *weight = if rng.gen_bool(0.5) {
+                    one[index]
+                } else {
+                    two[index]
+                };
+            }
+
+            candidates.push(mutate(child, &mut rng));
+        }
+    }
+
+    from_vector(best.1)
+}
+
+/// Standard normal sample (Box-Muller).
+fn gaussian(rng: &mut impl Rng) -> f64 {
+    let u: f64 = 1.0 - rng.gen::<f64>();
+    let v: f64 = rng.gen();
+
+    (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+}
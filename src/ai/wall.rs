@@ -4,21 +4,34 @@ use super::super::quoridor::{
     run::{Turn, AI},
 };
 
-use rand::{rngs::ThreadRng, Rng};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
-pub struct WallFirstMax {
-    rng: ThreadRng,
+pub struct WallFirstMax<R: Rng = StdRng> {
+    rng: R,
 }
 
 impl Default for WallFirstMax {
     fn default() -> Self {
         Self {
-            rng: rand::thread_rng(),
+            rng: StdRng::from_entropy(),
         }
     }
 }
 
-impl AI for WallFirstMax {
+impl WallFirstMax<StdRng> {
+    /// Picks the same walls every time for the same seed (and the same opponent).
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl<R: Rng> WallFirstMax<R> {
+    pub fn with_rng(rng: R) -> Self {
+        Self { rng }
+    }
+}
+
+impl<R: Rng> AI for WallFirstMax<R> {
     fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn {
         if player.walls > 0 {
             let walls = board::get_best_max_walls(board, player, enemy);
@@ -28,27 +41,36 @@ impl AI for WallFirstMax {
             }
         }
 
-        let mut path = board::get_path_to_goal(board, player, enemy);
-        let _current = path.pop_front().unwrap();
-        let next = path.pop_front().unwrap();
-
-        Turn::Move(next)
+        board::get_next_move(board, player, enemy).unwrap()
     }
 }
 
-pub struct WallFirstMinmax {
-    rng: ThreadRng,
+pub struct WallFirstMinmax<R: Rng = StdRng> {
+    rng: R,
 }
 
 impl Default for WallFirstMinmax {
     fn default() -> Self {
         Self {
-            rng: rand::thread_rng(),
+            rng: StdRng::from_entropy(),
         }
     }
 }
 
-impl AI for WallFirstMinmax {
+impl WallFirstMinmax<StdRng> {
+    /// Picks the same walls every time for the same seed (and the same opponent).
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl<R: Rng> WallFirstMinmax<R> {
+    pub fn with_rng(rng: R) -> Self {
+        Self { rng }
+    }
+}
+
+impl<R: Rng> AI for WallFirstMinmax<R> {
     fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn {
         if player.walls > 0 {
             let walls = board::get_best_minmax_walls(board, player, enemy);
@@ -58,10 +80,6 @@ impl AI for WallFirstMinmax {
             }
         }
 
-        let mut path = board::get_path_to_goal(board, player, enemy);
-        let _current = path.pop_front().unwrap();
-        let next = path.pop_front().unwrap();
-
-        Turn::Move(next)
+        board::get_next_move(board, player, enemy).unwrap()
     }
 }
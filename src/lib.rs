@@ -1,16 +1,55 @@
 pub mod ai;
 pub mod quoridor;
+pub mod render;
 
 #[cfg(test)]
 mod tests {
     use super::{
-        ai::moving::MoveOnly,
+        ai::{
+            analysis::{analyse, format_table},
+            annotate::{annotate, write_record, Judgement, Thresholds},
+            book::{Book, BookPlayer},
+            eval::{Evaluator, Feature, WeightedEval},
+            greedy::Greedy,
+            human::Human,
+            mcts::{Mcts, Parallelism},
+            moving::MoveOnly,
+            pruning::WallPruning,
+            random::{Random, RandomMoving},
+            registry::Registry,
+            search::{Search, WIN},
+            tt::{Bound, Entry, TranspositionTable},
+            tune::{self, GeneticOptions, TexelOptions},
+            wall::{WallFirstMax, WallFirstMinmax},
+        },
         quoridor::{
-            board,
-            run::{create_two_players, run},
+            board::{self, Board},
+            distance::distance_map,
+            game::{Game, IllegalTurn},
+            notation,
+            observer::{GameObserver, Recorder},
+            perft::{perft, perft_divide},
+            player::Player,
+            race::{self, Outcome, RaceTable},
+            run::{
+                create_two_players, run, run_with_observers, run_with_options, Context, GameEnd,
+                GameResult, RunOptions, Turn, WallData, AI,
+            },
+            stats::Statistics,
+            tournament::{play_game, play_match},
         },
     };
 
+    use super::render::{
+        ascii::Ascii,
+        compact::Compact,
+        svg::{Annotations, Svg},
+        unicode::Unicode,
+        Renderer,
+    };
+
+    use std::time::{Duration, Instant};
+
     #[test]
     fn valid_moves() {
         let board = board::create_new_board();
@@ -27,6 +66,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::useless_vec)]
     fn valid_walls() {
         let mut board = board::create_new_board();
         let (player_one, player_two) = create_two_players();
@@ -66,6 +106,57 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn walls_off_the_board() {
+        let board = board::create_new_board();
+        let (player_one, player_two) = create_two_players();
+        let width = board::get_board_width();
+        let height = board::get_board_height();
+
+        let illegal_walls = [
+            (false, width - 1, 0),
+            (true, width - 1, 0),
+            (false, 0, height - 1),
+            (true, 0, height - 1),
+            (true, width - 1, height - 1),
+            (false, usize::MAX, 0),
+            (true, 0, usize::MAX),
+        ];
+
+        assert!(illegal_walls.iter().all(|&wall| !board::can_place_wall(
+            &board,
+            &player_one,
+            &player_two,
+            wall
+        )));
+    }
+
+    #[test]
+    fn replays() {
+        let turns = [
+            Turn::Move((4, 1)),
+            Turn::Wall((false, 3, 6)),
+            Turn::Move((4, 2)),
+            Turn::Move((5, 8)),
+        ];
+
+        let game = Game::replay(&turns).unwrap();
+        assert_eq!((game.player_one.x, game.player_one.y), (4, 2));
+        assert_eq!((game.player_two.x, game.player_two.y), (5, 8));
+        assert_eq!(game.player_two.walls, 9);
+        assert!(game.player_one_turn);
+
+        let illegal = [Turn::Move((4, 1)), Turn::Wall((true, 8, 0))];
+        assert_eq!(
+            Game::replay(&illegal).err(),
+            Some(IllegalTurn {
+                index: 1,
+                turn: Turn::Wall((true, 8, 0)),
+            })
+        );
+        assert!(Game::replay(&[Turn::Move((4, 2))]).is_err());
+    }
+
     #[test]
     fn shortest_paths() {
         let board = board::create_new_board();
@@ -110,6 +201,1213 @@ mod tests {
     }
 
     #[test]
+    fn path_lengths() {
+        let mut board = board::create_new_board();
+        let (mut player_one, mut player_two) = create_two_players();
+
+        board::place_wall(&mut board, (false, 3, 3));
+        board::place_wall(&mut board, (true, 4, 4));
+        player_one.y = 4;
+        player_two.y = 5;
+
+        for (one, two) in [(&player_one, &player_two), (&player_two, &player_one)] {
+            assert_eq!(
+                board::get_path_len(&board, one, two),
+                board::get_path_to_goal(&board, one, two).len()
+            );
+        }
+    }
+
+    #[test]
+    fn distance_maps() {
+        let mut board = board::create_new_board();
+        let (player_one, _) = create_two_players();
+
+        let mut map = distance_map(&board, player_one.end_y);
+        assert_eq!(map.get(player_one.x, player_one.y), Some(8));
+        assert_eq!(map.path_from(&board, player_one.x, player_one.y).len(), 9);
+
+        let walls = [
+            (false, 0, 4),
+            (false, 2, 4),
+            (false, 4, 4),
+            (false, 6, 4),
+            (true, 7, 4),
+            (false, 7, 2),
+            (true, 3, 0),
+        ];
+
+        for wall in walls {
+            board::place_wall(&mut board, wall);
+            map.update_after_wall(&board, wall);
+
+            assert_eq!(
+                map.as_slice(),
+                distance_map(&board, player_one.end_y).as_slice()
+            );
+        }
+
+        assert_eq!(map.get(player_one.x, player_one.y), Some(12));
+        assert_eq!(map.get(8, 8), Some(0));
+    }
+
+    fn position(
+        player_one: (usize, usize, usize),
+        player_two: (usize, usize, usize),
+        walls: &[WallData],
+    ) -> Game {
+        let mut game = Game::new();
+        (game.player_one.x, game.player_one.y, game.player_one.walls) = player_one;
+        (game.player_two.x, game.player_two.y, game.player_two.walls) = player_two;
+        for &wall in walls {
+            board::place_wall(&mut game.board, wall);
+        }
+
+        game
+    }
+
+    #[test]
+    fn perft_start() {
+        let game = Game::new();
+
+        assert_eq!(perft(&game, 0), 1);
+        assert_eq!(perft(&game, 1), 131);
+        assert_eq!(perft(&game, 2), 16677);
+    }
+
+    #[test]
+    fn perft_jumps() {
+        let cases = [
+            // Straight jump
+            (position((4, 4, 0), (4, 5, 0), &[]), [4, 16, 64, 252]),
+            // Wall behind the enemy, both diagonals
+            (
+                position((4, 4, 0), (4, 5, 0), &[(false, 4, 5)]),
+                [5, 15, 58, 218],
+            ),
+            // Board edge behind the enemy
+            (position((4, 7, 0), (4, 8, 0), &[]), [5, 9, 38, 96]),
+            // Board edge behind the enemy, in the corner
+            (position((0, 7, 0), (0, 8, 0), &[]), [3, 4, 16, 33]),
+            // Wall behind the enemy and one diagonal walled off
+            (
+                position((4, 4, 0), (4, 5, 0), &[(false, 4, 5), (true, 4, 4)]),
+                [3, 6, 25, 87],
+            ),
+            // Only one wall left between both players
+            (
+                position((4, 4, 1), (4, 5, 0), &[(false, 4, 5), (true, 4, 4)]),
+                [124, 245, 1462, 5415],
+            ),
+        ];
+
+        for (game, counts) in cases {
+            for (depth, &count) in counts.iter().enumerate() {
+                assert_eq!(perft(&game, depth + 1), count);
+            }
+        }
+    }
+
+    #[test]
+    fn perft_divided() {
+        let game = position((4, 4, 0), (4, 5, 0), &[(false, 4, 5), (true, 4, 4)]);
+        let divide = perft_divide(&game, 2);
+
+        assert_eq!(
+            divide,
+            vec![
+                (Turn::Move((4, 3)), 2),
+                (Turn::Move((3, 5)), 2),
+                (Turn::Move((3, 4)), 2),
+            ]
+        );
+        assert_eq!(divide.iter().map(|(_, count)| count).sum::<u64>(), 6);
+    }
+
+    #[test]
+    fn observers() {
+        struct Counter {
+            starts: usize,
+            turns: usize,
+            illegal: Vec<Turn>,
+            ends: usize,
+        }
+
+        impl GameObserver for Counter {
+            fn game_start(&mut self, _game: &Game) {
+                self.starts += 1;
+            }
+
+            fn turn_played(&mut self, _game: &Game, _turn: Turn, _elapsed: Duration) {
+                self.turns += 1;
+            }
+
+            fn illegal_turn(&mut self, _game: &Game, turn: Turn) {
+                self.illegal.push(turn);
+            }
+
+            fn game_end(&mut self, _game: &Game, _result: &GameResult) {
+                self.ends += 1;
+            }
+        }
+
+        struct Cheater {}
+
+        impl AI for Cheater {
+            fn play(&mut self, _board: &Board, _player: &Player, _enemy: &Player) -> Turn {
+                Turn::Move((0, 8))
+            }
+        }
+
+        let mut counter = Counter {
+            starts: 0,
+            turns: 0,
+            illegal: Vec::new(),
+            ends: 0,
+        };
+        let mut recorder = Recorder::default();
+        let result = run_with_observers(
+            MoveOnly::default(),
+            MoveOnly::default(),
+            &mut [&mut counter, &mut recorder],
+        );
+
+        assert_eq!((counter.starts, counter.turns, counter.ends), (1, 14, 1));
+        assert_eq!(recorder.result, Some(result));
+
+        let turns: Vec<Turn> = recorder.turns.iter().map(|&(turn, _)| turn).collect();
+        assert_eq!(Game::replay(&turns).unwrap().winner(), Some(false));
+
+        let result = run_with_observers(MoveOnly::default(), Cheater {}, &mut [&mut counter]);
+
+        assert_eq!(counter.illegal, vec![Turn::Move((0, 8))]);
+        assert_eq!(result.end, GameEnd::IllegalTurn(Turn::Move((0, 8))));
+        assert!(result.winner);
+        assert_eq!(result.turns, 1);
+    }
+
+    #[test]
+    fn renderers() {
+        let last = Turn::Wall((true, 5, 1));
+        let game = Game::replay(&[Turn::Move((4, 1)), Turn::Wall((false, 3, 6)), last]).unwrap();
+
+        let compact = Compact {
+            labels: true,
+            highlight: true,
+        };
+        assert_eq!(
+            compact.render(&game.board, &game.player_one, &game.player_two, Some(last)),
+            "  a  b  c  d  e  f  g  h  i\n\
+             1 .  .  .  .  .  .  .  .  .\n\
+             2 .  .  .  .  x  . !.  .  .\n\
+             3 .  .  .  .  .  . !.  .  .\n\
+             4 .  .  .  .  .  .  .  .  .\n\
+             5 .  .  .  .  .  .  .  .  .\n\
+             6 .  .  .  .  .  .  .  .  .\n\
+             7 .  .  .  ._ ._ .  .  .  .\n\
+             8 .  .  .  .  .  .  .  .  .\n\
+             9 .  .  .  .  o  .  .  .  .\n"
+        );
+
+        let ascii = Ascii {
+            labels: false,
+            highlight: true,
+        }
+        .render(
+            &game.board,
+            &game.player_one,
+            &game.player_two,
+            Some(Turn::Move((4, 1))),
+        );
+        let lines: Vec<&str> = ascii.lines().collect();
+        assert_eq!(lines.len(), 2 * board::get_board_height() + 1);
+        assert_eq!(lines[3], "|                [x]    |           |");
+        assert_eq!(lines[14], "+   +   +   +---+---+   +   +   +   +");
+
+        assert_eq!(
+            game.to_string(),
+            Unicode::default().render(&game.board, &game.player_one, &game.player_two, None)
+        );
+    }
+
+    #[test]
+    fn svg() {
+        let turns = [
+            Turn::Move((4, 1)),
+            Turn::Wall((false, 3, 6)),
+            Turn::Wall((true, 5, 1)),
+            Turn::Wall((false, 5, 6)),
+            Turn::Wall((true, 5, 3)),
+        ];
+        let game = Game::replay(&turns).unwrap();
+
+        let mut walls = board::get_walls(&game.board);
+        walls.sort();
+        assert_eq!(
+            walls,
+            vec![(false, 3, 6), (false, 5, 6), (true, 5, 1), (true, 5, 3)]
+        );
+
+        let svg = Svg {
+            labels: true,
+            highlight: true,
+        };
+        let frames = svg.frames(&turns).unwrap();
+        assert_eq!(frames.len(), turns.len() + 1);
+        assert!(frames.iter().all(|frame| frame.starts_with("<svg")));
+        assert_eq!(
+            frames[5].matches("<rect").count(),
+            frames[0].matches("<rect").count() + 4
+        );
+
+        let animation = svg.animate(&turns, Duration::from_millis(500)).unwrap();
+        assert_eq!(animation.matches("<animate ").count(), turns.len() + 1);
+
+        let annotated = svg.render_annotated(
+            &game,
+            turns.last().copied(),
+            &Annotations {
+                suggested_turn: Some(Turn::Move((4, 7))),
+                paths: vec![board::get_path_to_goal(
+                    &game.board,
+                    &game.player_one,
+                    &game.player_two,
+                )],
+            },
+        );
+        assert_eq!(annotated.matches("<polyline").count(), 2);
+
+        assert_eq!(
+            svg.frames(&[Turn::Move((0, 0))]).err(),
+            Some(IllegalTurn {
+                index: 0,
+                turn: Turn::Move((0, 0))
+            })
+        );
+    }
+
+    #[test]
+    fn seeded_games() {
+        let record = |seed| {
+            let mut recorder = Recorder::default();
+            run_with_observers(
+                WallFirstMinmax::with_seed(seed),
+                Random::with_seed(seed + 1),
+                &mut [&mut recorder],
+            );
+
+            recorder
+                .turns
+                .into_iter()
+                .map(|(turn, _)| turn)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(record(7), record(7));
+
+        let one = play_match(3, 11, RandomMoving::with_seed, WallFirstMax::with_seed);
+        let two = play_match(3, 11, RandomMoving::with_seed, WallFirstMax::with_seed);
+        for (one, two) in one.games.iter().zip(&two.games) {
+            assert_eq!(one.seeds, two.seeds);
+            assert_eq!(one.result, two.result);
+            assert_eq!(
+                play_game(one.seeds, RandomMoving::with_seed, WallFirstMax::with_seed),
+                one.result
+            );
+        }
+    }
+
+    #[test]
+    fn weighted_eval() {
+        let game = Game::new();
+        let (player, enemy) = game.players();
+
+        assert_eq!(Feature::OwnPath.value(&game.board, player, enemy), 8.0);
+        assert_eq!(Feature::EnemyWalls.value(&game.board, player, enemy), 10.0);
+        assert_eq!(Feature::OwnMobility.value(&game.board, player, enemy), 3.0);
+        assert_eq!(
+            Feature::OwnShortestPaths.value(&game.board, player, enemy),
+            1f64.ln_1p()
+        );
+        assert_eq!(
+            WeightedEval::default().evaluate(&game.board, player, enemy),
+            0.0
+        );
+
+        // A wall in the way: step aside now or after the next step
+        let game = position((4, 6, 10), (4, 2, 10), &[(false, 4, 7)]);
+        let (player, enemy) = game.players();
+        assert_eq!(
+            Feature::OwnShortestPaths.value(&game.board, player, enemy),
+            2f64.ln_1p()
+        );
+
+        let weights: WeightedEval = "# race\nown_path = -1.5\n\nenemy_walls = 0.25 # walls\n"
+            .parse()
+            .unwrap();
+        assert_eq!(weights.weight(Feature::OwnPath), -1.5);
+        assert_eq!(weights.weight(Feature::EnemyWalls), 0.25);
+        assert_eq!(weights.weight(Feature::EnemyPath), 0.0);
+        assert_eq!(
+            weights.to_string().parse::<WeightedEval>().unwrap(),
+            weights
+        );
+        assert_eq!(
+            weights.evaluate(&game.board, player, enemy),
+            -1.5 * 3.0 + 0.25 * 10.0
+        );
+
+        assert_eq!(
+            "own_path = -1\nspeed = 2"
+                .parse::<WeightedEval>()
+                .err()
+                .unwrap()
+                .line,
+            2
+        );
+        assert!("own_path -1".parse::<WeightedEval>().is_err());
+        assert!("own_path = fast".parse::<WeightedEval>().is_err());
+
+        let result = run(Greedy::<WeightedEval>::default(), MoveOnly::default());
+        assert_eq!(result.end, GameEnd::Goal);
+    }
+
+    #[test]
+    fn tuning() {
+        let start = WeightedEval::default();
+        let samples = tune::self_play(4, 3, &start, 0.1);
+
+        assert!(!samples.is_empty());
+        assert!(samples
+            .iter()
+            .all(|sample| sample.result == 0.0 || sample.result == 1.0));
+
+        let options = TexelOptions {
+            iterations: 50,
+            ..TexelOptions::default()
+        };
+        let tuned = tune::texel(&samples, &start, &options);
+        assert!(
+            tune::texel_error(&samples, &tuned, options.scale)
+                < tune::texel_error(&samples, &start, options.scale)
+        );
+
+        let evolved = tune::genetic(
+            &start,
+            &GeneticOptions {
+                population: 2,
+                generations: 1,
+                games: 2,
+                ..GeneticOptions::default()
+            },
+        );
+        assert_eq!(evolved.weights.len(), Feature::ALL.len());
+
+        let path = std::env::temp_dir().join(format!(
+            "quoridor-tuning-weights-{}.txt",
+            std::process::id()
+        ));
+        tuned.save(&path).unwrap();
+        assert_eq!(WeightedEval::from_file(&path).unwrap(), tuned);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn notation() {
+        let turns = notation::parse_turns("e2 e8 a1h h8v i9").unwrap();
+        assert_eq!(
+            turns,
+            vec![
+                Turn::Move((4, 1)),
+                Turn::Move((4, 7)),
+                Turn::Wall((false, 0, 0)),
+                Turn::Wall((true, 7, 7)),
+                Turn::Move((8, 8)),
+            ]
+        );
+        for turn in turns {
+            assert_eq!(turn.to_string().parse(), Ok(turn));
+        }
+
+        for invalid in [
+            "", "e", "j1", "e0", "e10", "i9h", "e2x", "E2", "e+2", "e 2", "e+2h",
+        ] {
+            assert!(invalid.parse::<Turn>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn positions() {
+        let mut game = position((4, 2, 9), (4, 6, 8), &[(false, 3, 3), (true, 0, 0)]);
+        game.player_one_turn = false;
+        let text = notation::format_position(&game);
+        assert_eq!(text, "2 e3:9 e7:8 a1v d4h");
+
+        let parsed = notation::parse_position(&text).unwrap();
+        assert_eq!(parsed.hash(), game.hash());
+        assert_eq!(notation::format_position(&Game::new()), "1 e1:10 e9:10");
+
+        for invalid in [
+            "",
+            "3 e1:10 e9:10",
+            "1 e1:10",
+            "1 e1 e9:10",
+            "1 e1:x e9:10",
+            "1 e1:10 e1:10",
+            "1 e1:10 e9:10 e5",
+            "1 e1:10 e9:10 d4h d4v",
+        ] {
+            assert!(notation::parse_position(invalid).is_err(), "{}", invalid);
+        }
+
+        // Either notation for the same game
+        let replayed = notation::parse_game("e2 e8 d4h").unwrap();
+        assert_eq!(notation::format_position(&replayed), "2 e2:9 e8:10 d4h");
+        assert_eq!(
+            notation::parse_game("2 e2:9 e8:10 d4h").unwrap().hash(),
+            replayed.hash()
+        );
+        assert!(notation::parse_game("e2 e4").is_err());
+        assert!(notation::parse_game("3 e1:10 e9:10").is_err());
+    }
+
+    #[test]
+    fn analysis() {
+        let game = position((4, 7, 3), (0, 4, 3), &[]);
+
+        let analyses = analyse(&game, &mut WeightedEval::default());
+        assert_eq!(analyses.len(), game.get_valid_turns().len());
+        assert!(analyses
+            .windows(2)
+            .all(|pair| pair[0].score >= pair[1].score));
+
+        let mut search = Search::new(WeightedEval::default(), Duration::from_secs(60));
+        search.max_depth = 2;
+        let analyses = analyse(&game, &mut search);
+        let best = &analyses[0];
+        assert_eq!(best.turn, Turn::Move((4, 8)));
+        assert_eq!(best.score, WIN);
+        assert_eq!((best.path, best.enemy_path), (0, 4));
+        assert_eq!(best.pv, vec![Turn::Move((4, 8))]);
+
+        let table = format_table(&analyses);
+        assert_eq!(table.lines().count(), 1 + analyses.len());
+        assert_eq!(
+            table.lines().nth(1),
+            Some("   1  e9    1000000.00     0      4  e9")
+        );
+
+        // Every other turn lets the search look further
+        let wall = analyses
+            .iter()
+            .find(|analysis| matches!(analysis.turn, Turn::Wall(_)))
+            .unwrap();
+        assert_eq!(wall.pv[0], wall.turn);
+        assert!(wall.pv.len() > 1);
+        assert!(wall.score < WIN);
+    }
+
+    #[test]
+    fn annotations() {
+        // Player two walks into a lost race instead of using their walls
+        let record = "e2 e8 e3 e7 e4 e6 a1h d6 e5 d5 e6 d4 e7 d3 e8 d2 e9";
+        let turns = notation::parse_record(record).unwrap();
+
+        let mut search = Search::new(WeightedEval::default(), Duration::from_secs(60));
+        search.max_depth = 2;
+        let annotations = annotate(&turns, &mut search, &Thresholds::default()).unwrap();
+        assert_eq!(annotations.len(), turns.len());
+
+        let blunder = &annotations[15];
+        assert_eq!(blunder.turn, Turn::Move((3, 1)));
+        assert_eq!(blunder.judgement, Some(Judgement::Blunder));
+        assert!(matches!(blunder.better, Some(Turn::Wall(_))));
+        assert_eq!(annotations[16].after, WIN);
+        assert!(annotations
+            .iter()
+            .all(|annotation| annotation.judgement.is_some() == annotation.better.is_some()));
+
+        let written = write_record(&annotations);
+        assert!(written.contains("d2?? {blunder"));
+        assert_eq!(notation::parse_record(&written), Ok(turns));
+        assert!(notation::parse_record("e2 {unclosed").is_err());
+    }
+
+    #[test]
+    fn statistics() {
+        let record = "e2 e8 e3 e7 e4 e6 a1h d6 e5 d5 e6 d4 e7 d3 e8 d2 e9";
+        let mut stats = Statistics::new();
+        stats
+            .add_game(&notation::parse_record(record).unwrap(), None)
+            .unwrap();
+        stats.add_game(&[Turn::Move((4, 1))], None).unwrap();
+        assert!(stats.add_game(&[Turn::Move((4, 2))], None).is_err());
+
+        assert_eq!(
+            (stats.games, stats.decided, stats.player_one_wins),
+            (2, 1, 1)
+        );
+        assert_eq!(stats.turns, 18);
+        assert_eq!(stats.average_length(), 9.0);
+        assert_eq!(stats.player_one_win_rate(), 1.0);
+        assert_eq!(stats.walls_placed((false, 0, 0)), 1);
+        assert_eq!(stats.walls_placed((true, 0, 0)), 0);
+        let mut walls = Statistics::new();
+        walls.add_game(&[Turn::Wall((false, 0, 1))], None).unwrap();
+        assert_eq!(walls.walls_placed((false, 0, 1)), 1);
+        for off_board in [(false, 8, 0), (true, 0, 8), (false, usize::MAX, 0)] {
+            assert_eq!(walls.walls_placed(off_board), 0);
+        }
+        assert_eq!(stats.walls_at_ply(6), [1, 0]);
+        assert_eq!(stats.walls_at_ply(7), [0, 0]);
+        assert_eq!(stats.tile_visits(4, 0), [2, 0]);
+        assert_eq!(stats.tile_visits(4, 8), [1, 2]);
+        assert_eq!(stats.tile_visits(4, 1), [2, 0]);
+        assert_eq!(stats.tile_visits(13, 0), [0, 0]);
+        assert_eq!(stats.tile_visits(0, 9), [0, 0]);
+
+        let heat = stats.tile_heat(Some(true));
+        assert_eq!(heat[board::point_to_index(4, 1)], 1.0);
+        assert_eq!(heat[board::point_to_index(0, 0)], 0.0);
+        assert_eq!(stats.walls_csv().lines().count(), 1 + 128);
+        assert!(stats.walls_csv().contains("\nfalse,0,0,1\n"));
+        assert!(stats.tiles_csv().contains("\n4,8,1,2\n"));
+
+        let svg = Svg::default().heatmap(&stats.tile_heat(None), &stats.wall_heat());
+        assert_eq!(svg.matches("fill-opacity").count(), 14 + 1);
+        assert_eq!(svg.matches("#c8b89a").count(), board::get_board_size());
+
+        let mut stats = Statistics::new();
+        let result =
+            run_with_observers(MoveOnly::default(), MoveOnly::default(), &mut [&mut stats]);
+        assert_eq!((stats.games, stats.turns), (1, result.turns));
+        assert_eq!(stats.player_one_wins, result.winner as usize);
+    }
+
+    #[test]
+    fn human_player() {
+        let (player_one, player_two) = create_two_players();
+        let board = board::create_new_board();
+        let input = "e5\nxyz\n\ne2\ne3\n".as_bytes();
+        let mut output = Vec::new();
+
+        let turn = Human::new(input, &mut output).play(&board, &player_one, &player_two);
+        assert_eq!(turn, Turn::Move((4, 1)));
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.matches("your turn").count(), 4);
+        assert!(output.contains("e5 isn't legal here"));
+
+        // Giving up at the end of the input forfeits
+        let turn = Human::new("".as_bytes(), Vec::new()).play(&board, &player_two, &player_one);
+        assert_eq!(turn, Turn::Move((4, 8)));
+    }
+
+    #[test]
+    fn registry() {
+        let mut registry = Registry::default();
+        let names: Vec<&str> = registry.names().collect();
+        for name in [
+            "moveonly",
+            "random",
+            "randommoving",
+            "wallfirstmax",
+            "wallfirstminmax",
+        ] {
+            assert!(names.contains(&name), "{}", name);
+        }
+        for name in names {
+            assert!(registry.create(name, 1).is_ok(), "{}", name);
+        }
+
+        assert!(registry.create("WallFirstMax:seed=3", 1).is_ok());
+        assert!(registry
+            .create("mcts:playouts=50,threads=2,exploration=1.0", 1)
+            .is_ok());
+        assert!(registry
+            .create("search:ms=10,depth=2,ponder=true", 1)
+            .is_ok());
+        assert!(registry.create("MCTS:Playouts=50", 1).is_ok());
+        for invalid in [
+            "nobody",
+            "mcts:playouts",
+            "mcts:playouts=many",
+            "mcts:depth=3",
+            "random:seed=-1",
+            "mcts:playouts=10,playouts=20",
+            "mcts:playouts=10,PLAYOUTS=20",
+        ] {
+            assert!(registry.create(invalid, 1).is_err(), "{}", invalid);
+        }
+
+        // The same seed plays the same game, the parameter wins over the argument
+        let game = |registry: &Registry, spec: &str, seed: u64| {
+            let mut recorder = Recorder::default();
+            run_with_observers(
+                registry.create(spec, seed).unwrap(),
+                registry.create("moveonly", 0).unwrap(),
+                &mut [&mut recorder],
+            );
+            recorder
+                .turns
+                .iter()
+                .map(|&(turn, _)| turn)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            game(&registry, "random:seed=5", 1),
+            game(&registry, "random", 5)
+        );
+
+        registry.register("Stubborn", |params| {
+            let _ = params.get("patience", 0)?;
+            Ok(Box::new(MoveOnly::default()))
+        });
+        assert!(registry.create("stubborn:patience=3", 0).is_ok());
+        let result = run(
+            registry.create("STUBBORN", 0).unwrap(),
+            registry.create("randommoving", 0).unwrap(),
+        );
+        assert_eq!(result.end, GameEnd::Goal);
+    }
+
+    #[test]
+    fn boxed_ais() {
+        fn send<T: Send>(_: &T) {}
+        send(&Random::default());
+        send(&RandomMoving::default());
+        send(&WallFirstMax::default());
+        send(&WallFirstMinmax::default());
+        send(&BookPlayer::new(
+            Book::new(),
+            Search::<WeightedEval>::default(),
+        ));
+        send(&Human::default());
+
+        let mut search = Search::new(WeightedEval::default(), Duration::from_secs(60));
+        search.max_depth = 1;
+        let mut mcts = Mcts::with_seed(1);
+        mcts.playouts = 20;
+        let bots: Vec<Box<dyn AI + Send>> = vec![
+            Box::new(MoveOnly::default()),
+            Box::new(Random::with_seed(1)),
+            Box::new(RandomMoving::with_seed(1)),
+            Box::new(WallFirstMax::with_seed(1)),
+            Box::new(WallFirstMinmax::with_seed(1)),
+            Box::new(Greedy::new(WeightedEval::default())),
+            Box::new(search),
+            Box::new(mcts),
+            Box::new(BookPlayer::with_seed(Book::new(), MoveOnly::default(), 1)),
+        ];
+
+        let names: Vec<String> = bots.iter().map(|bot| bot.name()).collect();
+        assert_eq!(
+            names,
+            [
+                "MoveOnly",
+                "Random",
+                "RandomMoving",
+                "WallFirstMax",
+                "WallFirstMinmax",
+                "Greedy",
+                "Search",
+                "Mcts",
+                "MoveOnly+book",
+            ]
+        );
+
+        // Every bot plays a game on its own thread
+        let handles: Vec<_> = bots
+            .into_iter()
+            .map(|bot| std::thread::spawn(move || run(bot, MoveOnly::default())))
+            .collect();
+        for handle in handles {
+            let result = handle.join().unwrap();
+            assert!(!matches!(result.end, GameEnd::IllegalTurn(_)));
+        }
+
+        let result = play_match(
+            2,
+            0,
+            |seed| Box::new(Random::with_seed(seed)) as Box<dyn AI + Send>,
+            |_| Box::new(MoveOnly::default()) as Box<dyn AI + Send>,
+        );
+        assert_eq!(result.names, ("Random".to_string(), "MoveOnly".to_string()));
+        assert!(result.to_string().ends_with(&format!(
+            "Random {} - {} MoveOnly",
+            result.player_one_wins(),
+            result.player_two_wins()
+        )));
+    }
+
+    #[test]
+    fn game_context() {
+        #[derive(Default)]
+        struct Tracker {
+            side: Option<bool>,
+            seen: Vec<Turn>,
+            played: usize,
+            result: Option<GameResult>,
+        }
+
+        impl AI for Tracker {
+            fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn {
+                board::get_next_move(board, player, enemy).unwrap()
+            }
+
+            fn play_in_context(&mut self, context: &Context) -> Turn {
+                assert_eq!(Some(context.player_one), self.side);
+                assert_eq!(context.player_one, context.game.player_one_turn);
+                assert_eq!(context.turn_number(), 2 * self.played + 1);
+                assert_eq!(context.history.last(), self.seen.last());
+                assert!(context.time_left.is_none());
+
+                self.played += 1;
+                let (player, enemy) = context.players();
+                self.play(&context.game.board, player, enemy)
+            }
+
+            fn new_game(&mut self, context: &Context) {
+                assert_eq!(context.turn_number(), 0);
+                self.side = Some(context.player_one);
+            }
+
+            fn opponent_played(&mut self, turn: Turn) {
+                self.seen.push(turn);
+            }
+
+            fn game_over(&mut self, result: &GameResult) {
+                self.result = Some(*result);
+            }
+        }
+
+        let mut tracker = Tracker::default();
+        let mut recorder = Recorder::default();
+        let result = run_with_observers(MoveOnly::default(), &mut tracker, &mut [&mut recorder]);
+
+        assert_eq!(tracker.side, Some(false));
+        assert_eq!(tracker.result, Some(result));
+        let enemy_turns: Vec<Turn> = recorder
+            .turns
+            .iter()
+            .step_by(2)
+            .map(|&(turn, _)| turn)
+            .collect();
+        assert_eq!(tracker.seen, enemy_turns);
+        assert_eq!(tracker.played, result.turns / 2);
+    }
+
+    #[test]
+    fn clocks() {
+        struct Slow;
+
+        impl AI for Slow {
+            fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn {
+                std::thread::sleep(Duration::from_millis(20));
+                board::get_next_move(board, player, enemy).unwrap()
+            }
+        }
+
+        let options = RunOptions {
+            clock: Some(Duration::from_millis(50)),
+            ..RunOptions::default()
+        };
+        let result = run_with_options(MoveOnly::default(), Slow, &options, &mut []);
+        assert_eq!(result.end, GameEnd::Timeout);
+        assert!(result.winner);
+        assert_eq!(result.turns, 5);
+
+        // Search spends only part of what is left
+        let mut search = Search::new(WeightedEval::default(), Duration::from_secs(60));
+        let game = Game::new();
+        let context = Context {
+            game: &game,
+            history: &[],
+            player_one: true,
+            options: &options,
+            time_left: Some(Duration::from_millis(200)),
+        };
+        search.max_depth = 3;
+        let start = Instant::now();
+        search.play_in_context(&context);
+        assert!(start.elapsed() < Duration::from_millis(200));
+        assert!(search.info().unwrap().depth < search.max_depth);
+        assert_eq!(search.time_limit, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn search() {
+        let mut search = Search::new(WeightedEval::default(), Duration::from_secs(60));
+        search.max_depth = 2;
+
+        // Win right away
+        let game = position((4, 7, 0), (0, 4, 0), &[]);
+        let info = search.search(&game);
+        assert_eq!(info.pv[0], Turn::Move((4, 8)));
+        assert_eq!(info.score, WIN - 1.0);
+
+        // The enemy wins next turn unless a wall is placed
+        let game = position((0, 0, 10), (4, 1, 0), &[]);
+        let info = search.search(&game);
+        assert_eq!(info.depth, 2);
+        assert_eq!(info.pv.len(), 2);
+        assert!(matches!(info.pv[0], Turn::Wall(_)));
+        assert!(info.score > -WIN / 2.0);
+        assert!(info.nodes > 2);
+
+        let mut replay = game.clone();
+        for &turn in &info.pv {
+            assert!(replay.is_legal(turn));
+            replay.apply(turn);
+        }
+
+        // Runs out of time after the first iteration
+        let mut search = Search::new(WeightedEval::default(), Duration::ZERO);
+        let info = search.search(&Game::new());
+        assert_eq!(info.depth, 1);
+        assert_eq!(search.info(), Some(&info));
+
+        let mut other = Game::new();
+        assert_eq!(other.hash(), Game::new().hash());
+        other.player_one_turn = false;
+        assert_ne!(other.hash(), Game::new().hash());
+    }
+
+    #[test]
+    fn transposition_table() {
+        let table = TranspositionTable::new(4);
+        let game = Game::new();
+        let mut turns = game.get_valid_turns();
+        turns.push(Turn::Move((8, 8)));
+        turns.push(Turn::Wall((true, 7, 7)));
+
+        for (index, &turn) in turns.iter().enumerate() {
+            let entry = Entry {
+                depth: index % 7,
+                score: -(index as f64),
+                bound: Bound::Lower,
+                turn: Some(turn),
+            };
+            table.store(index as u64, entry);
+            assert_eq!(table.get(index as u64), Some(entry));
+            assert_eq!(table.get(index as u64 + 16), None);
+        }
+
+        // A deeper search of the same position is kept
+        let deep = Entry {
+            depth: 9,
+            score: WIN - 3.0,
+            bound: Bound::Exact,
+            turn: None,
+        };
+        table.store(1, deep);
+        table.store(1, Entry { depth: 2, ..deep });
+        assert_eq!(table.get(1), Some(deep));
+
+        table.clear();
+        assert_eq!(table.get(1), None);
+    }
+
+    #[test]
+    fn parallel_search() {
+        let game = position((4, 3, 10), (3, 5, 10), &[(false, 3, 4)]);
+        let search = |threads| {
+            let mut search = Search::new(WeightedEval::default(), Duration::from_secs(60));
+            search.max_depth = 3;
+            search.threads = threads;
+            search.search(&game)
+        };
+
+        let one = search(1);
+        let two = search(1);
+        assert_eq!(
+            (one.depth, one.nodes, one.score),
+            (two.depth, two.nodes, two.score)
+        );
+        assert_eq!(one.pv, two.pv);
+
+        let parallel = search(3);
+        assert_eq!(parallel.depth, 3);
+        assert!(game.is_legal(parallel.pv[0]));
+    }
+
+    #[test]
+    fn pondering() {
+        #[derive(Default)]
+        struct Ponderer {
+            starts: usize,
+            hits: usize,
+            misses: usize,
+            predicted: Option<Turn>,
+        }
+
+        impl AI for Ponderer {
+            fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn {
+                let turn = board::get_next_move(board, player, enemy).unwrap();
+                if let Turn::Move((x, y)) = turn {
+                    let player = Player { x, y, ..*player };
+                    self.predicted = board::get_next_move(board, enemy, &player);
+                }
+
+                turn
+            }
+
+            fn ponder_turn(&self) -> Option<Turn> {
+                self.predicted
+            }
+
+            fn ponder_start(
+                &mut self,
+                _board: &Board,
+                player: &Player,
+                enemy: &Player,
+                predicted: Turn,
+            ) {
+                assert_eq!(Some(predicted), self.predicted);
+                assert_eq!(predicted, Turn::Move((enemy.x, enemy.y)));
+                assert!(player.y != player.end_y);
+                self.starts += 1;
+            }
+
+            fn ponder_hit(&mut self) {
+                self.hits += 1;
+            }
+
+            fn ponder_miss(&mut self) {
+                self.misses += 1;
+            }
+        }
+
+        // `MoveOnly` always plays the predicted turn, there's nothing to ponder about its winning
+        // turn
+        let mut ponderer = Ponderer::default();
+        let result = run(&mut ponderer, MoveOnly::default());
+        assert!(!result.winner);
+        assert_eq!(ponderer.starts, result.turns / 2 - 1);
+        assert_eq!((ponderer.hits, ponderer.misses), (ponderer.starts, 0));
+
+        let mut ponderer = Ponderer::default();
+        run(RandomMoving::with_seed(2), &mut ponderer);
+        assert!(ponderer.misses > 1);
+        assert_eq!(ponderer.hits + ponderer.misses, ponderer.starts);
+
+        let mut search = Search::new(WeightedEval::default(), Duration::from_millis(20));
+        search.max_depth = 2;
+        search.ponder = true;
+        let result = run(&mut search, MoveOnly::default());
+        assert_eq!(result.end, GameEnd::Goal);
+    }
+
+    #[test]
+    fn opening_book() {
+        let mut book = Book::new();
+        let record = notation::parse_turns("e2 e8 e3 e7 e4").unwrap();
+        book.add_game(&record, None, 4).unwrap();
+        book.add_game(&record[..2], Some(true), 4).unwrap();
+        book.add_game(&notation::parse_turns("d1").unwrap(), None, 4)
+            .unwrap();
+
+        assert_eq!(book.len(), 4);
+        assert_eq!(
+            book.turns(&Game::new()),
+            &[(Turn::Move((4, 1)), 2), (Turn::Move((3, 0)), 1)]
+        );
+        assert_eq!(book.turns(&Game::replay(&record).unwrap()), &[]);
+        assert_eq!(
+            book.add_game(&[Turn::Move((0, 0))], None, 4),
+            Err(IllegalTurn {
+                index: 0,
+                turn: Turn::Move((0, 0))
+            })
+        );
+
+        let mut bytes = Vec::new();
+        book.write(&mut bytes).unwrap();
+        assert_eq!(Book::read(bytes.as_slice()).unwrap(), book);
+        assert!(Book::read(&b"QBK2"[..]).is_err());
+        assert!(Book::read(&bytes[..bytes.len() - 1]).is_err());
+
+        // Follows the book for two turns of its own, then it's up to `MoveOnly`
+        let mut recorder = Recorder::default();
+        run_with_observers(
+            BookPlayer::with_seed(book, MoveOnly::default(), 1),
+            BookPlayer::with_seed(Book::new(), MoveOnly::default(), 1),
+            &mut [&mut recorder],
+        );
+        let turns: Vec<Turn> = recorder.turns.iter().map(|&(turn, _)| turn).collect();
+        assert!(turns[0] == record[0] || turns[0] == Turn::Move((3, 0)));
+        if turns[0] == record[0] {
+            assert_eq!(turns[2], record[2]);
+        }
+
+        let book = Book::from_self_play(4, 6, 0, RandomMoving::with_seed, Random::with_seed);
+        assert!(!book.is_empty());
+        assert_eq!(
+            book,
+            Book::from_self_play(4, 6, 0, RandomMoving::with_seed, Random::with_seed)
+        );
+    }
+
+    #[test]
+    fn races() {
+        let game = position((0, 6, 0), (8, 1, 0), &[]);
+        assert_eq!(race::solve(&game).unwrap().0, Outcome::Loss(2));
+
+        let game = position((0, 7, 0), (8, 1, 0), &[]);
+        assert_eq!(
+            race::solve(&game),
+            Some((Outcome::Win(1), Some(Turn::Move((0, 8)))))
+        );
+        assert_eq!(race::solve(&position((0, 7, 1), (8, 1, 0), &[])), None);
+
+        // Player two gets to jump over player one
+        let game = position((4, 0, 0), (4, 8, 0), &[]);
+        let table = RaceTable::new(&game.board);
+        assert_eq!(table.outcome(&game), Outcome::Loss(16));
+
+        // Best play ends exactly as predicted
+        let game = position(
+            (2, 3, 0),
+            (3, 5, 0),
+            &[(false, 1, 4), (true, 3, 4), (false, 4, 2)],
+        );
+        let table = RaceTable::new(&game.board);
+        let outcome = table.outcome(&game);
+        let pv = table.principal_variation(&game, 100);
+        let mut end = game.clone();
+        for &turn in &pv {
+            assert!(end.is_legal(turn));
+            end.apply(turn);
+        }
+        match outcome {
+            Outcome::Win(n) => {
+                assert_eq!(pv.len(), n);
+                assert_eq!(end.winner(), Some(game.player_one_turn));
+            }
+            Outcome::Loss(n) => {
+                assert_eq!(pv.len(), n);
+                assert_eq!(end.winner(), Some(!game.player_one_turn));
+            }
+            Outcome::Draw => panic!("races always end"),
+        }
+
+        let mut search = Search::new(WeightedEval::default(), Duration::from_secs(60));
+        let info = search.search(&game);
+        assert_eq!(info.pv, pv);
+        assert!(info.score.abs() > WIN / 2.0);
+
+        // Playing player two, the race is run towards the first row
+        let game = position((0, 5, 0), (4, 2, 0), &[]);
+        let turn = search.play(&game.board, &game.player_two, &game.player_one);
+        assert_eq!(turn, Turn::Move((4, 1)));
+        assert!(search.info().unwrap().score > WIN / 2.0);
+
+        // Places all of its walls first
+        struct WallsFirst;
+
+        impl AI for WallsFirst {
+            fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn {
+                if player.walls > 0 {
+                    Turn::Wall(board::get_valid_walls(board, player, enemy)[0])
+                } else {
+                    board::get_next_move(board, player, enemy).unwrap()
+                }
+            }
+        }
+
+        let options = RunOptions {
+            adjudicate_races: true,
+            ..RunOptions::default()
+        };
+        let mut recorder = Recorder::default();
+        let result = run_with_options(WallsFirst, WallsFirst, &options, &mut [&mut recorder]);
+        assert_eq!(result.end, GameEnd::Adjudicated);
+        assert_eq!(result.turns, 20);
+
+        let turns: Vec<Turn> = recorder.turns.iter().map(|&(turn, _)| turn).collect();
+        let game = Game::replay(&turns).unwrap();
+        let winner = match race::solve(&game).unwrap().0 {
+            Outcome::Win(_) => game.player_one_turn,
+            _ => !game.player_one_turn,
+        };
+        assert_eq!(result.winner, winner);
+        assert_eq!(run(WallsFirst, WallsFirst).end, GameEnd::Goal);
+    }
+
+    #[test]
+    fn mcts() {
+        let search = |seed, threads, parallelism| {
+            let mut mcts = Mcts::with_seed(seed);
+            mcts.playouts = 200;
+            mcts.threads = threads;
+            mcts.parallelism = parallelism;
+            // Whoever doesn't win right away loses
+            mcts.search(&position((4, 7, 0), (0, 1, 0), &[]))
+        };
+
+        let stats = search(5, 1, Parallelism::Root);
+        assert_eq!(stats, search(5, 1, Parallelism::Tree));
+        assert_eq!(stats[0].turn, Turn::Move((4, 8)));
+        assert_eq!(stats[0].win_rate, 1.0);
+        assert_eq!(stats.iter().map(|stats| stats.visits).sum::<u32>(), 200);
+
+        for parallelism in [Parallelism::Root, Parallelism::Tree] {
+            let stats = search(5, 3, parallelism);
+            assert_eq!(stats[0].turn, Turn::Move((4, 8)));
+            assert_eq!(stats.iter().map(|stats| stats.visits).sum::<u32>(), 200);
+        }
+    }
+
+    #[test]
+    fn wall_pruning() {
+        let game = position((4, 2, 10), (4, 6, 10), &[(true, 0, 0)]);
+        let (player, enemy) = game.players();
+        let valid = board::get_valid_walls(&game.board, player, enemy);
+
+        assert_eq!(
+            WallPruning::OFF.get_walls(&game.board, player, enemy),
+            valid
+        );
+
+        let paths = WallPruning {
+            paths: true,
+            ..WallPruning::OFF
+        };
+        let walls = paths.get_walls(&game.board, player, enemy);
+        assert!(walls.iter().all(|wall| valid.contains(wall)));
+        assert!(walls.contains(&(false, 3, 2)));
+        assert!(walls.contains(&(false, 4, 5)));
+        assert!(!walls.contains(&(true, 7, 7)));
+
+        let adjacent = WallPruning {
+            adjacent: true,
+            ..WallPruning::OFF
+        };
+        let mut walls = adjacent.get_walls(&game.board, player, enemy);
+        walls.sort();
+        assert_eq!(
+            walls,
+            vec![(false, 0, 1), (false, 1, 0), (false, 1, 1), (true, 0, 2)]
+        );
+
+        let near = WallPruning {
+            near_pawns: Some(0),
+            ..WallPruning::OFF
+        };
+        let mut walls = near.get_walls(&game.board, player, enemy);
+        walls.sort();
+        assert_eq!(walls.len(), 16);
+        assert!(walls.contains(&(true, 3, 1)) && walls.contains(&(false, 4, 6)));
+
+        let stats = paths.stats([&game, &Game::new()]);
+        assert_eq!(stats.positions, 2);
+        assert_eq!(stats.valid, valid.len() + 128);
+        assert!(stats.kept < stats.valid);
+        assert_eq!(WallPruning::OFF.stats([&game]).ratio(), 1.0);
+    }
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_game() {
         for _ in 0..10 {
             let player_one = MoveOnly::default();
@@ -120,4 +1418,160 @@ mod tests {
             assert_eq!(result.turns, 14);
         }
     }
+
+    mod properties {
+        use crate::quoridor::{
+            board::{self, Board},
+            game::Game,
+            player::Player,
+            run::{Turn, WallData},
+        };
+
+        use proptest::prelude::*;
+
+        /// Plays the game picking `choices[i] % len` out of the legal turns, returns every
+        /// position along the way together with the walls placed so far.
+        fn random_game(choices: &[usize]) -> Vec<(Game, Vec<WallData>)> {
+            let mut game = Game::new();
+            let mut walls = Vec::new();
+            let mut positions = Vec::new();
+
+            for &choice in choices {
+                if game.winner().is_some() {
+                    break;
+                }
+
+                positions.push((game.clone(), walls.clone()));
+
+                let turns = game.get_valid_turns();
+                let turn = turns[choice % turns.len()];
+                if let Turn::Wall(wall) = turn {
+                    walls.push(wall);
+                }
+                game.apply(turn);
+            }
+
+            positions
+        }
+
+        fn is_open(board: &Board, (x, y): (usize, usize), (n_x, n_y): (usize, usize)) -> bool {
+            if n_x >= board::get_board_width() || n_y >= board::get_board_height() {
+                return false;
+            }
+
+            match (n_x as isize - x as isize, n_y as isize - y as isize) {
+                (1, 0) => board[board::point_to_index(x, y)].right,
+                (-1, 0) => board[board::point_to_index(n_x, n_y)].right,
+                (0, 1) => board[board::point_to_index(x, y)].down,
+                (0, -1) => board[board::point_to_index(n_x, n_y)].down,
+                _ => false,
+            }
+        }
+
+        fn is_legal_move(
+            board: &Board,
+            player: &Player,
+            enemy: &Player,
+            to: (usize, usize),
+        ) -> bool {
+            let from = (player.x, player.y);
+            let enemy = (enemy.x, enemy.y);
+
+            if to == enemy || to == from {
+                return false;
+            }
+            if is_open(board, from, to) {
+                return true;
+            }
+            if !is_open(board, from, enemy) {
+                return false;
+            }
+
+            let behind = (
+                (2 * enemy.0).wrapping_sub(from.0),
+                (2 * enemy.1).wrapping_sub(from.1),
+            );
+            if to == behind {
+                return is_open(board, enemy, to);
+            }
+
+            !is_open(board, enemy, behind) && is_open(board, enemy, to)
+        }
+
+        fn overlaps((vert, x, y): WallData, (other_vert, other_x, other_y): WallData) -> bool {
+            if vert != other_vert {
+                return x == other_x && y == other_y;
+            }
+
+            if vert {
+                x == other_x && y.abs_diff(other_y) <= 1
+            } else {
+                y == other_y && x.abs_diff(other_x) <= 1
+            }
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(32))]
+
+            #[test]
+            fn walls_leave_a_path(choices in prop::collection::vec(any::<usize>(), 1..20)) {
+                for (game, _) in random_game(&choices) {
+                    let (player, enemy) = game.players();
+
+                    for wall in board::get_valid_walls(&game.board, player, enemy) {
+                        let mut board = game.board.clone();
+                        board::place_wall(&mut board, wall);
+
+                        prop_assert!(!board::get_path_to_goal(&board, player, enemy).is_empty());
+                        prop_assert!(!board::get_path_to_goal(&board, enemy, player).is_empty());
+                    }
+                }
+            }
+
+            #[test]
+            fn moves_are_steps_or_jumps(choices in prop::collection::vec(any::<usize>(), 1..60)) {
+                for (game, _) in random_game(&choices) {
+                    let (player, enemy) = game.players();
+
+                    for turn in board::get_valid_moves(&game.board, player, enemy) {
+                        let Turn::Move(to) = turn else {
+                            panic!("get_valid_moves returned {:?}", turn);
+                        };
+
+                        prop_assert!(is_legal_move(&game.board, player, enemy, to), "{:?}", to);
+                    }
+
+                    let next = board::get_next_move(&game.board, player, enemy).unwrap();
+                    prop_assert!(game.is_legal(next), "{:?}", next);
+                }
+            }
+
+            #[test]
+            fn walls_never_overlap(choices in prop::collection::vec(any::<usize>(), 1..20)) {
+                for (game, walls) in random_game(&choices) {
+                    let (player, enemy) = game.players();
+
+                    for wall in board::get_valid_walls(&game.board, player, enemy) {
+                        prop_assert!(
+                            walls.iter().all(|&placed| !overlaps(placed, wall)),
+                            "{:?} overlaps {:?}",
+                            wall,
+                            walls
+                        );
+                    }
+                }
+            }
+
+            #[test]
+            fn indices_round_trip(
+                x in 0..board::get_board_width(),
+                y in 0..board::get_board_height(),
+            ) {
+                let index = board::point_to_index(x, y);
+
+                prop_assert!(index < board::get_board_size());
+                prop_assert_eq!(board::index_to_point(index), (x, y));
+            }
+        }
+    }
 }
@@ -0,0 +1,309 @@
+use quoridor::{
+    ai::{
+        analysis::{analyse, format_table},
+        eval::WeightedEval,
+        human::Human,
+        registry::Registry,
+        search::Search,
+    },
+    quoridor::{
+        game::Game,
+        notation,
+        observer::GameObserver,
+        perft::{perft, perft_divide},
+        run::{run_with_options, GameEnd, RunOptions, AI},
+        tournament::play_match,
+    },
+    render::{ascii::Ascii, compact::Compact, unicode::Unicode, Printer, Renderer},
+};
+
+use std::{collections::HashMap, env, fs, process, thread, time::Duration};
+
+const USAGE: &str = "usage: quoridor <command> [arguments] [--option value ...]
+
+commands:
+  play <ai|human> <ai|human>  play a game and watch it
+                              --seed <n> --delay <ms> --renderer <unicode|ascii|compact>
+                              --adjudicate --clock <ms per player>
+  match <ai> <ai>             play a number of games and sum them up
+                              --games <n> --seed <n>
+  analyse <position>          score every legal turn of a position or of the turns played
+                              --depth <n> (0 only evaluates)
+  replay <file>               show a game of a record file, one game per line
+                              --game <n> --delay <ms> --renderer <unicode|ascii|compact>
+  perft [position]            count the positions after every number of turns
+                              --depth <n> --divide
+
+AIs are given as a name, optionally with parameters like `mcts:playouts=5000,threads=2`:
+";
+
+/// Positional arguments and `--key value` options, `--flag` alone has an empty value.
+struct Args {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+impl Args {
+    fn parse(args: impl Iterator<Item = String>) -> Self {
+        let mut positional = Vec::new();
+        let mut options = HashMap::new();
+        let mut args = args.peekable();
+
+        while let Some(arg) = args.next() {
+            match arg.strip_prefix("--") {
+                Some(key) => {
+                    let value = args
+                        .next_if(|value| !value.starts_with("--"))
+                        .unwrap_or_default();
+                    options.insert(key.to_string(), value);
+                }
+                None => positional.push(arg),
+            }
+        }
+
+        Self {
+            positional,
+            options,
+        }
+    }
+
+    fn positional(&self, index: usize, name: &str) -> Result<&str, String> {
+        self.positional
+            .get(index)
+            .map(String::as_str)
+            .ok_or_else(|| format!("missing {}", name))
+    }
+
+    fn number<T: std::str::FromStr>(&self, key: &str, default: T) -> Result<T, String> {
+        match self.options.get(key) {
+            Some(value) => value
+                .parse()
+                .map_err(|_| format!("--{} must be a number, not `{}`", key, value)),
+            None => Ok(default),
+        }
+    }
+
+    fn flag(&self, key: &str) -> bool {
+        self.options.contains_key(key)
+    }
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let command = args.next().unwrap_or_default();
+    let args = Args::parse(args);
+
+    let result = match command.as_str() {
+        "play" => play(&args),
+        "match" => play_games(&args),
+        "analyse" => analyse_position(&args),
+        "replay" => replay(&args),
+        "perft" => count(&args),
+        "" | "help" | "--help" => {
+            println!("{}", usage());
+            return;
+        }
+        _ => Err(format!("unknown command `{}`", command)),
+    };
+
+    if let Err(error) = result {
+        eprintln!("error: {}\n\n{}", error, usage());
+        process::exit(2);
+    }
+}
+
+fn usage() -> String {
+    let registry = registry();
+    let names: Vec<&str> = registry.names().collect();
+    format!("{}{}", USAGE, names.join(", "))
+}
+
+/// The shipped AIs and a human at the keyboard.
+fn registry() -> Registry {
+    let mut registry = Registry::default();
+    registry.register("human", |_| Ok(Box::new(Human::default())));
+
+    registry
+}
+
+fn player(registry: &Registry, spec: &str, seed: u64) -> Result<Box<dyn AI + Send>, String> {
+    registry
+        .create(spec, seed)
+        .map_err(|error| error.to_string())
+}
+
+fn renderer(args: &Args) -> Result<Box<dyn Renderer>, String> {
+    let renderer: Box<dyn Renderer> = match args.options.get("renderer").map(String::as_str) {
+        None | Some("unicode") => Box::new(Unicode {
+            labels: true,
+            highlight: true,
+        }),
+        Some("ascii") => Box::new(Ascii {
+            labels: true,
+            highlight: true,
+        }),
+        Some("compact") => Box::new(Compact {
+            labels: true,
+            highlight: true,
+        }),
+        Some(other) => return Err(format!("unknown renderer `{}`", other)),
+    };
+
+    Ok(renderer)
+}
+
+/// Reads a position like `1 e1:10 e9:10` or the turns played from the start.
+fn position(text: &str) -> Result<Game, String> {
+    notation::parse_game(text).map_err(|error| error.to_string())
+}
+
+fn play(args: &Args) -> Result<(), String> {
+    let seed = args.number("seed", rand::random())?;
+    let registry = registry();
+    let player_one = player(&registry, args.positional(0, "the first player")?, seed)?;
+    let player_two = player(
+        &registry,
+        args.positional(1, "the second player")?,
+        seed.wrapping_add(1),
+    )?;
+    let delay = Duration::from_millis(args.number("delay", 0)?);
+    let options = RunOptions {
+        adjudicate_races: args.flag("adjudicate"),
+        clock: match args.options.get("clock") {
+            Some(_) => Some(Duration::from_millis(args.number("clock", 0)?)),
+            None => None,
+        },
+    };
+
+    let mut printer = Printer::new(renderer(args)?, delay);
+    println!(
+        "{} (x) against {} (o)",
+        player_one.name(),
+        player_two.name()
+    );
+    let result = run_with_options(
+        player_one,
+        player_two,
+        &options,
+        &mut [&mut printer as &mut dyn GameObserver],
+    );
+    match result.end {
+        GameEnd::Goal => {}
+        GameEnd::IllegalTurn(turn) => println!("The loser played the illegal turn {}", turn),
+        GameEnd::Adjudicated => println!("The race was adjudicated"),
+        GameEnd::Timeout => println!("The loser ran out of time"),
+    }
+
+    Ok(())
+}
+
+fn play_games(args: &Args) -> Result<(), String> {
+    let names = [
+        args.positional(0, "the first AI")?,
+        args.positional(1, "the second AI")?,
+    ];
+    let registry = registry();
+    // Only the spec decides whether an AI can be created, not the seed, so once both are
+    // created here every game of the match can create them too
+    for name in names {
+        player(&registry, name, 0)?;
+    }
+    let games = args.number("games", 20)?;
+    let seed = args.number("seed", rand::random())?;
+    let create = |name: &str, seed| {
+        registry
+            .create(name, seed)
+            .expect("the spec was already created successfully")
+    };
+
+    let result = play_match(
+        games,
+        seed,
+        |seed| create(names[0], seed),
+        |seed| create(names[1], seed),
+    );
+    println!("{}", result);
+
+    Ok(())
+}
+
+fn analyse_position(args: &Args) -> Result<(), String> {
+    let game = position(args.positional(0, "the position")?)?;
+    let depth = args.number("depth", 2)?;
+
+    let analyses = if depth == 0 {
+        analyse(&game, &mut WeightedEval::default())
+    } else {
+        let mut search = Search::new(WeightedEval::default(), Duration::MAX);
+        search.max_depth = depth;
+        analyse(&game, &mut search)
+    };
+
+    println!("{}", notation::format_position(&game));
+    print!("{}", format_table(&analyses));
+
+    Ok(())
+}
+
+fn replay(args: &Args) -> Result<(), String> {
+    let file = args.positional(0, "the record file")?;
+    let records = fs::read_to_string(file).map_err(|error| format!("{}: {}", file, error))?;
+    let index = args.number("game", 1usize)?;
+    let line = records
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .nth(index.saturating_sub(1))
+        .ok_or_else(|| format!("{} has no game {}", file, index))?;
+    let turns = notation::parse_record(line).map_err(|error| error.to_string())?;
+    let renderer = renderer(args)?;
+    let delay = Duration::from_millis(args.number("delay", 0)?);
+
+    let mut game = Game::new();
+    println!(
+        "{}",
+        renderer.render(&game.board, &game.player_one, &game.player_two, None)
+    );
+
+    for (ply, &turn) in turns.iter().enumerate() {
+        if !game.is_legal(turn) {
+            return Err(format!("turn {} ({}) is illegal", ply + 1, turn));
+        }
+        game.apply(turn);
+
+        thread::sleep(delay);
+        println!("{}. {}", ply + 1, turn);
+        println!(
+            "{}",
+            renderer.render(&game.board, &game.player_one, &game.player_two, Some(turn))
+        );
+    }
+
+    if let Some(winner) = game.winner() {
+        println!("Player {} won", if winner { "x" } else { "o" });
+    }
+
+    Ok(())
+}
+
+fn count(args: &Args) -> Result<(), String> {
+    let game = match args.positional.first() {
+        Some(text) => position(text)?,
+        None => Game::new(),
+    };
+    let depth = args.number("depth", 2)?;
+
+    if args.flag("divide") {
+        let mut total = 0;
+        for (turn, nodes) in perft_divide(&game, depth) {
+            println!("{} {}", turn, nodes);
+            total += nodes;
+        }
+        println!("total {}", total);
+    } else {
+        for depth in 0..=depth {
+            println!("depth {} nodes {}", depth, perft(&game, depth));
+        }
+    }
+
+    Ok(())
+}
@@ -1,9 +1,10 @@
 use super::{
+    path::PathFinder,
     player::Player,
     run::{MoveData, Turn, WallData},
 };
 
-use std::collections::{HashSet, VecDeque};
+use std::collections::VecDeque;
 
 const BOARD_WIDTH: usize = 9;
 const BOARD_HEIGHT: usize = 9;
@@ -17,6 +18,7 @@ pub struct Node {
     pub down: bool,
 }
 
+#[derive(Clone, Copy)]
 enum Direction {
     Up,
     Down,
@@ -67,7 +69,8 @@ pub fn can_place_wall(
 ) -> bool {
     let (vert, x, y) = wall;
 
-    if x > BOARD_WIDTH - 1 || y > BOARD_HEIGHT - 1 {
+    // A wall is two tiles long, so it can't be anchored on the last column or row
+    if x >= BOARD_WIDTH - 1 || y >= BOARD_HEIGHT - 1 {
         return false;
     }
 
@@ -86,8 +89,8 @@ pub fn can_place_wall(
     let mut board = board.clone();
     place_wall(&mut board, wall);
 
-    !get_path_to_goal(&board, player_one, player_two).is_empty()
-        && !get_path_to_goal(&board, player_two, player_one).is_empty()
+    get_path_len(&board, player_one, player_two) != 0
+        && get_path_len(&board, player_two, player_one) != 0
 }
 
 fn wall_heuristic(
@@ -100,12 +103,12 @@ fn wall_heuristic(
     place_wall(&mut board, wall);
 
     (
-        get_path_to_goal(&board, player_one, player_two).len(),
-        get_path_to_goal(&board, player_two, player_one).len(),
+        get_path_len(&board, player_one, player_two),
+        get_path_len(&board, player_two, player_one),
     )
 }
 
-pub(crate) fn place_wall(board: &mut Board, wall: WallData) {
+pub fn place_wall(board: &mut Board, wall: WallData) {
     if wall.0 {
         let one = point_to_index(wall.1, wall.2);
         let two = point_to_index(wall.1, wall.2 + 1);
@@ -121,6 +124,50 @@ pub(crate) fn place_wall(board: &mut Board, wall: WallData) {
     }
 }
 
+/// FNV-1a hash of the walls on `board`, the same between runs and platforms.
+pub fn hash(board: &Board) -> u64 {
+    board.iter().fold(0xcbf2_9ce4_8422_2325, |hash, node| {
+        fnv(hash, node.right as u8 | (node.down as u8) << 1)
+    })
+}
+
+/// Adds `byte` to an FNV-1a `hash`.
+pub(crate) fn fnv(hash: u64, byte: u8) -> u64 {
+    (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+}
+
+/// Recovers the walls placed on `board`. A board only stores blocked edges, so a run of blocked
+/// edges is split into walls from the top/left, which is exact for every legal position.
+pub fn get_walls(board: &Board) -> Vec<WallData> {
+    let mut walls = Vec::new();
+
+    for x in 0..BOARD_WIDTH - 1 {
+        let mut y = 0;
+        while y < BOARD_HEIGHT - 1 {
+            if !board[point_to_index(x, y)].right && !board[point_to_index(x, y + 1)].right {
+                walls.push((true, x, y));
+                y += 2;
+            } else {
+                y += 1;
+            }
+        }
+    }
+
+    for y in 0..BOARD_HEIGHT - 1 {
+        let mut x = 0;
+        while x < BOARD_WIDTH - 1 {
+            if !board[point_to_index(x, y)].down && !board[point_to_index(x + 1, y)].down {
+                walls.push((false, x, y));
+                x += 2;
+            } else {
+                x += 1;
+            }
+        }
+    }
+
+    walls
+}
+
 fn can_move(board: &Board, x: usize, y: usize, direction: Direction) -> bool {
     match direction {
         Direction::Up => y > 0 && board[point_to_index(x, y - 1)].down,
@@ -130,6 +177,21 @@ fn can_move(board: &Board, x: usize, y: usize, direction: Direction) -> bool {
     }
 }
 
+/// Indices of the tiles reachable in a single step from `index`, ignoring pawns.
+pub(crate) fn get_neighbours(board: &Board, index: usize) -> impl Iterator<Item = usize> + '_ {
+    let (x, y) = index_to_point(index);
+
+    [
+        (Direction::Up, index.wrapping_sub(BOARD_WIDTH)),
+        (Direction::Down, index + BOARD_WIDTH),
+        (Direction::Left, index.wrapping_sub(1)),
+        (Direction::Right, index + 1),
+    ]
+    .into_iter()
+    .filter(move |(direction, _)| can_move(board, x, y, *direction))
+    .map(|(_, neighbour)| neighbour)
+}
+
 pub fn get_valid_moves(board: &Board, player_one: &Player, player_two: &Player) -> Vec<Turn> {
     let x = player_one.x;
     let y = player_one.y;
@@ -277,153 +339,43 @@ pub fn get_path_to_goal(
     player_one: &Player,
     player_two: &Player,
 ) -> VecDeque<MoveData> {
-    let root = point_to_index(player_one.x, player_one.y);
-    let mut open = HashSet::new();
-
-    let mut came_from = vec![usize::MAX; get_board_size()];
-    let mut g_score = vec![usize::MAX; get_board_size()];
-    let mut f_score = vec![usize::MAX; get_board_size()];
-
-    open.insert(root);
-    g_score[root] = 0;
-
-    while !open.is_empty() {
-        let current = *open
-            .iter()
-            .min_by(|&&x, &&y| f_score[x].cmp(&f_score[y]))
-            .unwrap();
-        open.remove(&current);
-
-        let (x, y) = index_to_point(current);
-
-        if y == player_one.end_y {
-            let mut path = VecDeque::from([(x, y)]);
-            let mut current = current;
-            let player_two_index = point_to_index(player_two.x, player_two.y);
-
-            while current != root {
-                current = came_from[current];
-                if player_two_index != current {
-                    let (x, y) = index_to_point(current);
-                    path.push_front((x, y));
-                }
-            }
-
-            return path;
-        }
-
-        // Check up
-        if y != 0 {
-            let n_x = x;
-            let n_y = y - 1;
-            let n_index = point_to_index(n_x, n_y);
-            if board[n_index].down {
-                expand_node(
-                    &mut open,
-                    &mut came_from,
-                    &mut g_score,
-                    &mut f_score,
-                    current,
-                    n_index,
-                    n_x,
-                    n_y,
-                    player_one.end_y,
-                    player_two,
-                );
-            }
-        }
-
-        // Check down
-        if y != get_board_height() - 1 {
-            let n_x = x;
-            let n_y = y + 1;
-            let n_index = point_to_index(n_x, n_y);
-            if board[current].down {
-                expand_node(
-                    &mut open,
-                    &mut came_from,
-                    &mut g_score,
-                    &mut f_score,
-                    current,
-                    n_index,
-                    n_x,
-                    n_y,
-                    player_one.end_y,
-                    player_two,
-                );
-            }
-        }
-
-        // Check left
-        if x != 0 {
-            let n_x = x - 1;
-            let n_y = y;
-            let n_index = point_to_index(n_x, n_y);
-            if board[n_index].right {
-                expand_node(
-                    &mut open,
-                    &mut came_from,
-                    &mut g_score,
-                    &mut f_score,
-                    current,
-                    n_index,
-                    n_x,
-                    n_y,
-                    player_one.end_y,
-                    player_two,
-                );
-            }
-        }
+    PathFinder::with(|path_finder| path_finder.path_to_goal(board, player_one, player_two))
+}
 
-        // Check right
-        if x != get_board_width() - 1 {
-            let n_x = x + 1;
-            let n_y = y;
-            let n_index = point_to_index(n_x, n_y);
-            if board[current].right {
-                expand_node(
-                    &mut open,
-                    &mut came_from,
-                    &mut g_score,
-                    &mut f_score,
-                    current,
-                    n_index,
-                    n_x,
-                    n_y,
-                    player_one.end_y,
-                    player_two,
-                );
-            }
-        }
+/// The next step towards the goal. That's the first step of `get_path_to_goal` if it is a legal
+/// move, otherwise (the path may cut diagonally past the enemy where only a straight jump is
+/// allowed) the legal move that leaves the shortest path.
+pub fn get_next_move(board: &Board, player_one: &Player, player_two: &Player) -> Option<Turn> {
+    let moves = get_valid_moves(board, player_one, player_two);
+    let next = get_path_to_goal(board, player_one, player_two)
+        .get(1)
+        .copied()?;
+
+    if moves.contains(&Turn::Move(next)) {
+        return Some(Turn::Move(next));
     }
 
-    VecDeque::new()
+    moves
+        .into_iter()
+        .filter_map(|turn| match turn {
+            Turn::Move((x, y)) => {
+                let player = Player {
+                    x,
+                    y,
+                    ..*player_one
+                };
+                match get_path_len(board, &player, player_two) {
+                    0 => None,
+                    len => Some((len, turn)),
+                }
+            }
+            Turn::Wall(_) => None,
+        })
+        .min_by_key(|&(len, _)| len)
+        .map(|(_, turn)| turn)
 }
 
-fn expand_node(
-    open: &mut HashSet<usize>,
-    came_from: &mut Vec<usize>,
-    g_score: &mut Vec<usize>,
-    f_score: &mut Vec<usize>,
-    current: usize,
-    n_index: usize,
-    n_x: usize,
-    n_y: usize,
-    end_y: usize,
-    player_two: &Player,
-) {
-    let cost = if n_x == player_two.x && n_y == player_two.y {
-        0
-    } else {
-        1
-    };
-    let score = g_score[current] + cost;
-    if score < g_score[n_index] {
-        came_from[n_index] = current;
-        g_score[n_index] = score;
-        f_score[n_index] = score + n_y.abs_diff(end_y);
-        if !open.contains(&n_index) {
-            open.insert(n_index);
-        }
-    }
+/// Length of `get_path_to_goal` (0 if there is no path), without allocating the path.
+pub fn get_path_len(board: &Board, player_one: &Player, player_two: &Player) -> usize {
+    PathFinder::with(|path_finder| path_finder.path_len(board, player_one, player_two))
 }
@@ -0,0 +1,170 @@
+use super::{
+    board::{self, Board},
+    run::{MoveData, WallData},
+};
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
+
+pub const UNREACHABLE: usize = usize::MAX;
+
+/// Distance from every tile of the board to a goal row, ignoring pawns.
+#[derive(Clone)]
+pub struct DistanceMap {
+    goal: usize,
+    distances: Vec<usize>,
+}
+
+/// Builds the distance map for `goal` with a reverse BFS starting from every tile of the goal row.
+pub fn distance_map(board: &Board, goal: usize) -> DistanceMap {
+    let mut distances = vec![UNREACHABLE; board::get_board_size()];
+    let mut queue = VecDeque::new();
+
+    for x in 0..board::get_board_width() {
+        let index = board::point_to_index(x, goal);
+        distances[index] = 0;
+        queue.push_back(index);
+    }
+
+    while let Some(current) = queue.pop_front() {
+        for neighbour in board::get_neighbours(board, current) {
+            if distances[neighbour] == UNREACHABLE {
+                distances[neighbour] = distances[current] + 1;
+                queue.push_back(neighbour);
+            }
+        }
+    }
+
+    DistanceMap { goal, distances }
+}
+
+impl DistanceMap {
+    pub fn goal(&self) -> usize {
+        self.goal
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<usize> {
+        match self.distances[board::point_to_index(x, y)] {
+            UNREACHABLE => None,
+            distance => Some(distance),
+        }
+    }
+
+    /// Distances indexed the same way as the board, `UNREACHABLE` for tiles cut off from the goal.
+    pub fn as_slice(&self) -> &[usize] {
+        &self.distances
+    }
+
+    /// Updates the map after `wall` has been placed on `board`.
+    ///
+    /// A wall can only make distances grow, so only the tiles that lost every neighbour one step
+    /// closer to the goal (and the tiles that relied on them) are recomputed.
+    pub fn update_after_wall(&mut self, board: &Board, wall: WallData) {
+        let distances = &mut self.distances;
+        let mut affected = vec![false; distances.len()];
+        let mut queue = BinaryHeap::new();
+
+        for (one, two) in blocked_edges(wall) {
+            if distances[one] != UNREACHABLE && distances[one] == distances[two].wrapping_add(1) {
+                queue.push(Reverse((distances[one], one)));
+            }
+            if distances[two] != UNREACHABLE && distances[two] == distances[one].wrapping_add(1) {
+                queue.push(Reverse((distances[two], two)));
+            }
+        }
+
+        // Tiles are visited by increasing distance, so every tile that could support `current`
+        // has already been classified when `current` is popped.
+        while let Some(Reverse((distance, current))) = queue.pop() {
+            if affected[current] || distance == 0 {
+                continue;
+            }
+
+            let supported = board::get_neighbours(board, current).any(|neighbour| {
+                !affected[neighbour] && distances[neighbour].wrapping_add(1) == distance
+            });
+            if supported {
+                continue;
+            }
+
+            affected[current] = true;
+            for neighbour in board::get_neighbours(board, current) {
+                if distances[neighbour] == distance + 1 && !affected[neighbour] {
+                    queue.push(Reverse((distance + 1, neighbour)));
+                }
+            }
+        }
+
+        for (index, _) in affected.iter().enumerate().filter(|(_, &a)| a) {
+            distances[index] = UNREACHABLE;
+        }
+
+        for (index, _) in affected.iter().enumerate().filter(|(_, &a)| a) {
+            let best = board::get_neighbours(board, index)
+                .filter(|&neighbour| !affected[neighbour])
+                .map(|neighbour| distances[neighbour])
+                .min()
+                .unwrap_or(UNREACHABLE);
+
+            if best != UNREACHABLE {
+                distances[index] = best + 1;
+                queue.push(Reverse((best + 1, index)));
+            }
+        }
+
+        while let Some(Reverse((distance, current))) = queue.pop() {
+            if distance > distances[current] {
+                continue;
+            }
+
+            for neighbour in board::get_neighbours(board, current) {
+                if affected[neighbour] && distance + 1 < distances[neighbour] {
+                    distances[neighbour] = distance + 1;
+                    queue.push(Reverse((distance + 1, neighbour)));
+                }
+            }
+        }
+    }
+
+    /// Reads a shortest path from `(x, y)` to the goal row off the map, following decreasing
+    /// distances. Like `board::get_path_to_goal`, the path starts with `(x, y)` itself and is
+    /// empty if the goal can't be reached.
+    pub fn path_from(&self, board: &Board, x: usize, y: usize) -> VecDeque<MoveData> {
+        let mut current = board::point_to_index(x, y);
+        if self.distances[current] == UNREACHABLE {
+            return VecDeque::new();
+        }
+
+        let mut path = VecDeque::from([(x, y)]);
+        while self.distances[current] != 0 {
+            current = board::get_neighbours(board, current)
+                .find(|&neighbour| self.distances[neighbour] + 1 == self.distances[current])
+                .unwrap();
+            path.push_back(board::index_to_point(current));
+        }
+
+        path
+    }
+}
+
+fn blocked_edges((vert, x, y): WallData) -> [(usize, usize); 2] {
+    if vert {
+        [
+            (board::point_to_index(x, y), board::point_to_index(x + 1, y)),
+            (
+                board::point_to_index(x, y + 1),
+                board::point_to_index(x + 1, y + 1),
+            ),
+        ]
+    } else {
+        [
+            (board::point_to_index(x, y), board::point_to_index(x, y + 1)),
+            (
+                board::point_to_index(x + 1, y),
+                board::point_to_index(x + 1, y + 1),
+            ),
+        ]
+    }
+}
@@ -0,0 +1,159 @@
+use super::{
+    board::{self, Board},
+    player::Player,
+    run::{create_two_players, Turn},
+};
+
+/// The first turn of a replay that isn't legal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IllegalTurn {
+    pub index: usize,
+    pub turn: Turn,
+}
+
+/// A position: the board, both players and whose turn it is.
+#[derive(Clone)]
+pub struct Game {
+    pub board: Board,
+    pub player_one: Player,
+    pub player_two: Player,
+    pub player_one_turn: bool,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Game {
+    pub fn new() -> Self {
+        let (player_one, player_two) = create_two_players();
+
+        Self {
+            board: board::create_new_board(),
+            player_one,
+            player_two,
+            player_one_turn: true,
+        }
+    }
+
+    /// The position `AI::play` is asked about, telling the players apart by their goal row.
+    pub fn from_players(board: &Board, player: &Player, enemy: &Player) -> Self {
+        let player_one_turn = player.end_y == board::get_board_height() - 1;
+        let (player_one, player_two) = if player_one_turn {
+            (*player, *enemy)
+        } else {
+            (*enemy, *player)
+        };
+
+        Self {
+            board: board.clone(),
+            player_one,
+            player_two,
+            player_one_turn,
+        }
+    }
+
+    /// The player to move and their enemy.
+    pub fn players(&self) -> (&Player, &Player) {
+        if self.player_one_turn {
+            (&self.player_one, &self.player_two)
+        } else {
+            (&self.player_two, &self.player_one)
+        }
+    }
+
+    /// `Some(true)` if player one reached their goal, `Some(false)` if player two did.
+    pub fn winner(&self) -> Option<bool> {
+        if self.player_one.y == self.player_one.end_y {
+            Some(true)
+        } else if self.player_two.y == self.player_two.end_y {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// A hash of the position that stays the same between runs and platforms (FNV-1a), so it
+    /// can be stored in files.
+    pub fn hash(&self) -> u64 {
+        let mut hash = board::hash(&self.board);
+
+        for player in [&self.player_one, &self.player_two] {
+            hash = board::fnv(hash, player.x as u8);
+            hash = board::fnv(hash, player.y as u8);
+            hash = board::fnv(hash, player.walls as u8);
+        }
+
+        board::fnv(hash, self.player_one_turn as u8)
+    }
+
+    /// Every legal turn for the player to move, walls only if they have any left.
+    pub fn get_valid_turns(&self) -> Vec<Turn> {
+        let (player, enemy) = self.players();
+        let mut turns = board::get_valid_moves(&self.board, player, enemy);
+
+        if player.walls > 0 {
+            turns.extend(
+                board::get_valid_walls(&self.board, player, enemy)
+                    .into_iter()
+                    .map(Turn::Wall),
+            );
+        }
+
+        turns
+    }
+
+    /// Whether the player to move may play `turn`.
+    pub fn is_legal(&self, turn: Turn) -> bool {
+        if self.winner().is_some() {
+            return false;
+        }
+
+        let (player, enemy) = self.players();
+        match turn {
+            Turn::Move(_) => board::get_valid_moves(&self.board, player, enemy).contains(&turn),
+            Turn::Wall(wall) => {
+                player.walls > 0 && board::can_place_wall(&self.board, player, enemy, wall)
+            }
+        }
+    }
+
+    /// Plays `turns` from the starting position, stopping at the first illegal one.
+    pub fn replay(turns: &[Turn]) -> Result<Self, IllegalTurn> {
+        let mut game = Self::new();
+
+        for (index, &turn) in turns.iter().enumerate() {
+            if !game.is_legal(turn) {
+                return Err(IllegalTurn { index, turn });
+            }
+            game.apply(turn);
+        }
+
+        Ok(game)
+    }
+
+    /// Plays `turn` for the player to move and hands the turn over. Like `run`, this doesn't
+    /// check whether the turn is legal.
+    pub fn apply(&mut self, turn: Turn) {
+        let player = if self.player_one_turn {
+            &mut self.player_one
+        } else {
+            &mut self.player_two
+        };
+
+        match turn {
+            Turn::Move((x, y)) => {
+                player.x = x;
+                player.y = y;
+            }
+            Turn::Wall(wall) => {
+                player.walls -= 1;
+                board::place_wall(&mut self.board, wall);
+            }
+        }
+
+        self.player_one_turn = !self.player_one_turn;
+    }
+}
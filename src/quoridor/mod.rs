@@ -1,3 +1,12 @@
 pub mod board;
+pub mod distance;
+pub mod game;
+pub mod notation;
+pub mod observer;
+pub mod path;
+pub mod perft;
 pub mod player;
+pub mod race;
 pub mod run;
+pub mod stats;
+pub mod tournament;
@@ -0,0 +1,201 @@
+//! Turns written the way the coordinate labels of the renderers read: `e2` moves to column e,
+//! row 2 and `e2h`/`e2v` places a horizontal/vertical wall whose top left tile is e2. Positions
+//! are written as a line of text made of those.
+
+use super::{board, game::Game, run::Turn};
+use crate::render::{column_label, row_label};
+
+use std::{error::Error, fmt, str::FromStr};
+
+impl fmt::Display for Turn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Turn::Move((x, y)) => write!(f, "{}{}", column_label(x), row_label(y)),
+            Turn::Wall((vert, x, y)) => write!(
+                f,
+                "{}{}{}",
+                column_label(x),
+                row_label(y),
+                if vert { 'v' } else { 'h' }
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseTurnError(pub String);
+
+impl fmt::Display for ParseTurnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid turn `{}`", self.0)
+    }
+}
+
+impl Error for ParseTurnError {}
+
+impl FromStr for Turn {
+    type Err = ParseTurnError;
+
+    /// Only checks that the turn is on the board, not that it is legal.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseTurnError(s.to_string());
+        let mut chars = s.chars();
+
+        let column = chars.next().ok_or_else(error)?;
+        if !column.is_ascii_lowercase() {
+            return Err(error());
+        }
+        let x = (column as u8 - b'a') as usize;
+
+        let rest = chars.as_str();
+        let (row, wall) = match rest.strip_suffix('h') {
+            Some(row) => (row, Some(false)),
+            None => match rest.strip_suffix('v') {
+                Some(row) => (row, Some(true)),
+                None => (rest, None),
+            },
+        };
+        if row.is_empty() || !row.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(error());
+        }
+        let y = row
+            .parse::<usize>()
+            .ok()
+            .and_then(|row| row.checked_sub(1))
+            .ok_or_else(error)?;
+
+        match wall {
+            None if x < board::get_board_width() && y < board::get_board_height() => {
+                Ok(Turn::Move((x, y)))
+            }
+            Some(vert) if x < board::get_board_width() - 1 && y < board::get_board_height() - 1 => {
+                Ok(Turn::Wall((vert, x, y)))
+            }
+            _ => Err(error()),
+        }
+    }
+}
+
+/// Parses turns separated by whitespace.
+pub fn parse_turns(s: &str) -> Result<Vec<Turn>, ParseTurnError> {
+    s.split_whitespace().map(str::parse).collect()
+}
+
+/// Parses a game record: turns separated by whitespace, each optionally followed by an
+/// annotation like `?`, `??`, `?!` or `!`, with comments in braces in between.
+pub fn parse_record(s: &str) -> Result<Vec<Turn>, ParseTurnError> {
+    let mut turns = Vec::new();
+    let mut rest = s;
+
+    while let Some(start) = rest.find('{') {
+        turns.extend(parse_turns(&strip_annotations(&rest[..start]))?);
+        rest = match rest[start..].find('}') {
+            Some(end) => &rest[start + end + 1..],
+            None => return Err(ParseTurnError(rest[start..].to_string())),
+        };
+    }
+    turns.extend(parse_turns(&strip_annotations(rest))?);
+
+    Ok(turns)
+}
+
+fn strip_annotations(s: &str) -> String {
+    s.split_whitespace()
+        .map(|turn| turn.trim_end_matches(['?', '!']))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Writes a position as the player to move (`1` or `2`), both pawns with their walls left and
+/// the walls on the board, like `2 e2:10 e9:9 d4h`.
+pub fn format_position(game: &Game) -> String {
+    let mut out = format!(
+        "{} {}:{} {}:{}",
+        if game.player_one_turn { 1 } else { 2 },
+        Turn::Move((game.player_one.x, game.player_one.y)),
+        game.player_one.walls,
+        Turn::Move((game.player_two.x, game.player_two.y)),
+        game.player_two.walls
+    );
+
+    for wall in board::get_walls(&game.board) {
+        out.push(' ');
+        out.push_str(&Turn::Wall(wall).to_string());
+    }
+
+    out
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParsePositionError(pub String);
+
+impl fmt::Display for ParsePositionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for ParsePositionError {}
+
+/// Reads a position written by `format_position`. Walls have to fit on the board and leave
+/// both players a path, like placed walls do.
+pub fn parse_position(s: &str) -> Result<Game, ParsePositionError> {
+    let error = |message: String| ParsePositionError(message);
+    let mut fields = s.split_whitespace();
+    let mut game = Game::new();
+
+    game.player_one_turn = match fields.next() {
+        Some("1") => true,
+        Some("2") => false,
+        other => {
+            return Err(error(format!(
+                "expected the player to move (1 or 2), got `{}`",
+                other.unwrap_or("")
+            )))
+        }
+    };
+
+    for player in [&mut game.player_one, &mut game.player_two] {
+        let field = fields
+            .next()
+            .ok_or_else(|| error("expected a pawn like `e1:10`".to_string()))?;
+        let (square, walls) = field
+            .split_once(':')
+            .ok_or_else(|| error(format!("expected a pawn like `e1:10`, got `{}`", field)))?;
+
+        match square.parse() {
+            Ok(Turn::Move((x, y))) => (player.x, player.y) = (x, y),
+            _ => return Err(error(format!("invalid square `{}`", square))),
+        }
+        player.walls = walls
+            .parse()
+            .map_err(|_| error(format!("invalid number of walls `{}`", walls)))?;
+    }
+
+    if (game.player_one.x, game.player_one.y) == (game.player_two.x, game.player_two.y) {
+        return Err(error("both pawns are on the same square".to_string()));
+    }
+
+    for field in fields {
+        let wall = match field.parse() {
+            Ok(Turn::Wall(wall)) => wall,
+            _ => return Err(error(format!("invalid wall `{}`", field))),
+        };
+        if !board::can_place_wall(&game.board, &game.player_one, &game.player_two, wall) {
+            return Err(error(format!("wall `{}` can't be placed", field)));
+        }
+
+        board::place_wall(&mut game.board, wall);
+    }
+
+    Ok(game)
+}
+
+/// Reads a position like `parse_position` or, if it isn't one, the turns played from the start.
+pub fn parse_game(s: &str) -> Result<Game, ParsePositionError> {
+    parse_position(s).or_else(|error| {
+        let turns = parse_turns(s).map_err(|_| error)?;
+        Game::replay(&turns)
+            .map_err(|illegal| ParsePositionError(format!("illegal turn {}", illegal.turn)))
+    })
+}
@@ -0,0 +1,48 @@
+use super::{
+    game::Game,
+    run::{GameResult, Turn},
+};
+
+use std::time::Duration;
+
+/// Gets notified by `run_with_observers` about everything that happens in a game.
+///
+/// Every callback does nothing by default, so observers only implement what they care about.
+#[allow(unused_variables)]
+pub trait GameObserver {
+    /// Called once with the starting position, before the first turn.
+    fn game_start(&mut self, game: &Game) {}
+
+    /// Called after `turn` has been applied to `game`, so the player that played it is the one
+    /// that is *not* to move. `elapsed` is the time the AI took to pick the turn.
+    fn turn_played(&mut self, game: &Game, turn: Turn, elapsed: Duration) {}
+
+    /// Called with the position the player to move tried to play `turn` in. The game ends right
+    /// after, the player forfeits.
+    fn illegal_turn(&mut self, game: &Game, turn: Turn) {}
+
+    /// Called once with the final position.
+    fn game_end(&mut self, game: &Game, result: &GameResult) {}
+}
+
+/// Keeps every turn of the game along with how long it took to play.
+#[derive(Default)]
+pub struct Recorder {
+    pub turns: Vec<(Turn, Duration)>,
+    pub result: Option<GameResult>,
+}
+
+impl GameObserver for Recorder {
+    fn game_start(&mut self, _game: &Game) {
+        self.turns.clear();
+        self.result = None;
+    }
+
+    fn turn_played(&mut self, _game: &Game, turn: Turn, elapsed: Duration) {
+        self.turns.push((turn, elapsed));
+    }
+
+    fn game_end(&mut self, _game: &Game, result: &GameResult) {
+        self.result = Some(*result);
+    }
+}
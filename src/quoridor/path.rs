@@ -0,0 +1,128 @@
+use super::{
+    board::{self, Board},
+    player::Player,
+    run::MoveData,
+};
+
+use std::{cell::RefCell, collections::VecDeque};
+
+const UNVISITED: usize = usize::MAX;
+
+/// Shortest-path search with reusable scratch buffers.
+///
+/// Every step costs 1, except stepping onto the enemy pawn which costs 0 (the pawn gets jumped),
+/// so a 0-1 BFS over a deque finds the shortest path without a priority queue.
+pub struct PathFinder {
+    came_from: Vec<usize>,
+    distances: Vec<usize>,
+    open: VecDeque<usize>,
+}
+
+thread_local! {
+    static PATH_FINDER: RefCell<PathFinder> = RefCell::new(PathFinder::new());
+}
+
+impl Default for PathFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PathFinder {
+    pub fn new() -> Self {
+        Self {
+            came_from: vec![UNVISITED; board::get_board_size()],
+            distances: vec![UNVISITED; board::get_board_size()],
+            open: VecDeque::with_capacity(board::get_board_size()),
+        }
+    }
+
+    /// Runs `f` with the path finder of the current thread, `f` must not call `with` again.
+    pub(crate) fn with<T>(f: impl FnOnce(&mut PathFinder) -> T) -> T {
+        PATH_FINDER.with(|path_finder| f(&mut path_finder.borrow_mut()))
+    }
+
+    /// Same as `board::get_path_to_goal`.
+    pub fn path_to_goal(
+        &mut self,
+        board: &Board,
+        player_one: &Player,
+        player_two: &Player,
+    ) -> VecDeque<MoveData> {
+        let root = board::point_to_index(player_one.x, player_one.y);
+        let Some(goal) = self.search(board, player_one, player_two) else {
+            return VecDeque::new();
+        };
+
+        let mut path = VecDeque::from([board::index_to_point(goal)]);
+        let mut current = goal;
+        let player_two_index = board::point_to_index(player_two.x, player_two.y);
+
+        while current != root {
+            current = self.came_from[current];
+            if player_two_index != current {
+                path.push_front(board::index_to_point(current));
+            }
+        }
+
+        path
+    }
+
+    /// Length of the path `path_to_goal` would return, without building it.
+    pub fn path_len(&mut self, board: &Board, player_one: &Player, player_two: &Player) -> usize {
+        let root = board::point_to_index(player_one.x, player_one.y);
+        let Some(goal) = self.search(board, player_one, player_two) else {
+            return 0;
+        };
+
+        let mut len = 1;
+        let mut current = goal;
+        let player_two_index = board::point_to_index(player_two.x, player_two.y);
+
+        while current != root {
+            current = self.came_from[current];
+            if player_two_index != current {
+                len += 1;
+            }
+        }
+
+        len
+    }
+
+    /// 0-1 BFS from `player_one` to its goal row, returns the index of the goal tile reached.
+    fn search(&mut self, board: &Board, player_one: &Player, player_two: &Player) -> Option<usize> {
+        let root = board::point_to_index(player_one.x, player_one.y);
+        let player_two_index = board::point_to_index(player_two.x, player_two.y);
+
+        self.distances.fill(UNVISITED);
+        self.open.clear();
+
+        self.distances[root] = 0;
+        self.open.push_back(root);
+
+        while let Some(current) = self.open.pop_front() {
+            let (_, y) = board::index_to_point(current);
+            if y == player_one.end_y {
+                return Some(current);
+            }
+
+            for neighbour in board::get_neighbours(board, current) {
+                let jump = neighbour == player_two_index;
+                let distance = self.distances[current] + if jump { 0 } else { 1 };
+
+                if distance < self.distances[neighbour] {
+                    self.distances[neighbour] = distance;
+                    self.came_from[neighbour] = current;
+
+                    if jump {
+                        self.open.push_front(neighbour);
+                    } else {
+                        self.open.push_back(neighbour);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,42 @@
+use super::{game::Game, run::Turn};
+
+/// Counts the positions reachable in exactly `depth` turns. Games that are over before `depth`
+/// is reached don't count, the same way checkmates don't in chess perft.
+pub fn perft(game: &Game, depth: usize) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    if game.winner().is_some() {
+        return 0;
+    }
+
+    let turns = game.get_valid_turns();
+    if depth == 1 {
+        return turns.len() as u64;
+    }
+
+    turns
+        .into_iter()
+        .map(|turn| {
+            let mut game = game.clone();
+            game.apply(turn);
+            perft(&game, depth - 1)
+        })
+        .sum()
+}
+
+/// `perft` split by the first turn, to narrow down which subtree a wrong count comes from.
+pub fn perft_divide(game: &Game, depth: usize) -> Vec<(Turn, u64)> {
+    if depth == 0 || game.winner().is_some() {
+        return Vec::new();
+    }
+
+    game.get_valid_turns()
+        .into_iter()
+        .map(|turn| {
+            let mut child = game.clone();
+            child.apply(turn);
+            (turn, perft(&child, depth - 1))
+        })
+        .collect()
+}
@@ -0,0 +1,210 @@
+//! Exact solutions of pawn races: once neither player has walls left, the board can't change
+//! anymore and every placement of the two pawns can be solved by retrograde analysis.
+
+use super::{
+    board::{self, Board},
+    game::Game,
+    player::Player,
+    run::Turn,
+};
+
+/// The game-theoretic value of a position for the player to move, counted in plies (turns of
+/// either player) until the game ends with best play.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Wins with their `n`th ply from now, `Win(1)` wins right away.
+    Win(usize),
+    /// Loses after `n` plies, `Loss(0)` has lost already.
+    Loss(usize),
+    /// Neither player can force a win.
+    Draw,
+}
+
+/// Every pawn placement on one board, solved. Player one races to the last row and player two
+/// to the first, as in `Game::new`.
+pub struct RaceTable {
+    board: Board,
+    outcomes: Vec<Option<Outcome>>,
+    /// Moves of the player to move, for every state.
+    moves: Vec<Vec<Turn>>,
+}
+
+fn state(player_one: usize, player_two: usize, player_one_turn: bool) -> usize {
+    (player_one * board::get_board_size() + player_two) * 2 + player_one_turn as usize
+}
+
+fn pawn(index: usize, end_y: usize) -> Player {
+    let (x, y) = board::index_to_point(index);
+    Player {
+        x,
+        y,
+        end_y,
+        walls: 0,
+    }
+}
+
+impl RaceTable {
+    pub fn new(board: &Board) -> Self {
+        let size = board::get_board_size();
+        let one_goal = board::get_board_height() - 1;
+        let mut outcomes = vec![None; size * size * 2];
+        let mut moves = vec![Vec::new(); size * size * 2];
+
+        for one in 0..size {
+            for two in (0..size).filter(|&two| two != one) {
+                let player_one = pawn(one, one_goal);
+                let player_two = pawn(two, 0);
+
+                for player_one_turn in [true, false] {
+                    let index = state(one, two, player_one_turn);
+                    let (player, enemy) = if player_one_turn {
+                        (&player_one, &player_two)
+                    } else {
+                        (&player_two, &player_one)
+                    };
+
+                    if enemy.y == enemy.end_y {
+                        outcomes[index] = Some(Outcome::Loss(0));
+                    } else if player.y != player.end_y {
+                        moves[index] = board::get_valid_moves(board, player, enemy);
+                    }
+                }
+            }
+        }
+
+        let mut table = Self {
+            board: board.clone(),
+            outcomes,
+            moves,
+        };
+        table.solve();
+
+        table
+    }
+
+    /// Resolves the states one ply further from the end at a time: a state is won in `n` if a
+    /// move leads to a loss in `n - 1` and lost in `n` once every move leads to a win in at most
+    /// `n - 1`. What's left after nothing changes anymore is a draw.
+    fn solve(&mut self) {
+        for plies in 1.. {
+            let mut resolved = Vec::new();
+
+            for (index, moves) in self.moves.iter().enumerate() {
+                if self.outcomes[index].is_some() || moves.is_empty() {
+                    continue;
+                }
+
+                let mut all_won = true;
+                let mut wins = false;
+                for &turn in moves {
+                    match self.outcomes[self.successor(index, turn)] {
+                        Some(Outcome::Loss(n)) if n + 1 == plies => wins = true,
+                        Some(Outcome::Win(_)) => {}
+                        _ => all_won = false,
+                    }
+                }
+
+                if wins {
+                    resolved.push((index, Outcome::Win(plies)));
+                } else if all_won {
+                    resolved.push((index, Outcome::Loss(plies)));
+                }
+            }
+
+            // Every layer only depends on the one before, after an empty one nothing changes
+            if resolved.is_empty() {
+                break;
+            }
+
+            for (index, outcome) in resolved {
+                self.outcomes[index] = Some(outcome);
+            }
+        }
+    }
+
+    fn successor(&self, index: usize, turn: Turn) -> usize {
+        let size = board::get_board_size();
+        let player_one_turn = index % 2 == 1;
+        let one = index / 2 / size;
+        let two = index / 2 % size;
+        let to = match turn {
+            Turn::Move((x, y)) => board::point_to_index(x, y),
+            Turn::Wall(_) => unreachable!("races have no walls"),
+        };
+
+        if player_one_turn {
+            state(to, two, false)
+        } else {
+            state(one, to, true)
+        }
+    }
+
+    /// Whether this table was solved for the walls of `game`.
+    pub fn matches(&self, game: &Game) -> bool {
+        board::hash(&self.board) == board::hash(&game.board)
+    }
+
+    fn index(game: &Game) -> usize {
+        state(
+            board::point_to_index(game.player_one.x, game.player_one.y),
+            board::point_to_index(game.player_two.x, game.player_two.y),
+            game.player_one_turn,
+        )
+    }
+
+    /// The outcome for the player to move in `game`, which has to be on the board of this table.
+    pub fn outcome(&self, game: &Game) -> Outcome {
+        self.outcomes[Self::index(game)].unwrap_or(Outcome::Draw)
+    }
+
+    /// The quickest win, the slowest loss or a move keeping a draw.
+    pub fn best_turn(&self, game: &Game) -> Option<Turn> {
+        let index = Self::index(game);
+        let wanted = match self.outcome(game) {
+            Outcome::Win(n) => Some(Outcome::Loss(n - 1)),
+            Outcome::Loss(0) => return None,
+            Outcome::Loss(n) => Some(Outcome::Win(n - 1)),
+            Outcome::Draw => None,
+        };
+
+        self.moves[index]
+            .iter()
+            .copied()
+            .find(|&turn| self.outcomes[self.successor(index, turn)] == wanted)
+    }
+
+    /// The turns of both players from `game` on with best play, until the game ends or, for a
+    /// draw, `limit` turns.
+    pub fn principal_variation(&self, game: &Game, limit: usize) -> Vec<Turn> {
+        let mut game = game.clone();
+        let mut turns = Vec::new();
+
+        while turns.len() < limit {
+            match self.best_turn(&game) {
+                Some(turn) => {
+                    game.apply(turn);
+                    turns.push(turn);
+                }
+                None => break,
+            }
+        }
+
+        turns
+    }
+}
+
+/// Whether `game` is a pure pawn race.
+pub fn is_race(game: &Game) -> bool {
+    game.player_one.walls == 0 && game.player_two.walls == 0
+}
+
+/// The exact outcome of a race and the best turn for the player to move, `None` if `game` isn't
+/// a race.
+pub fn solve(game: &Game) -> Option<(Outcome, Option<Turn>)> {
+    if !is_race(game) {
+        return None;
+    }
+
+    let table = RaceTable::new(&game.board);
+    Some((table.outcome(game), table.best_turn(game)))
+}
@@ -1,163 +1,415 @@
 use super::{
     board::{self, Board},
+    game::Game,
+    observer::GameObserver,
     player::Player,
+    race::{self, Outcome, RaceTable},
 };
 
-#[cfg(feature = "print_game")]
-use std::{thread, time::Duration};
+use std::{
+    any,
+    time::{Duration, Instant},
+};
 
 pub type WallData = (bool, usize, usize);
 pub type MoveData = (usize, usize);
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Turn {
     Move(MoveData),
     Wall(WallData),
 }
 
+/// What an AI can know about the game it plays, besides the position.
+#[derive(Clone, Copy)]
+pub struct Context<'a> {
+    pub game: &'a Game,
+    /// Every turn played so far by both players, the first one by player one.
+    pub history: &'a [Turn],
+    /// Whether the AI plays player one.
+    pub player_one: bool,
+    /// The rules the game is played by.
+    pub options: &'a RunOptions,
+    /// What is left on the AI's clock, if the game is played with `RunOptions::clock`.
+    pub time_left: Option<Duration>,
+}
+
+impl Context<'_> {
+    /// The number of turns played so far.
+    pub fn turn_number(&self) -> usize {
+        self.history.len()
+    }
+
+    /// The AI's own player and their enemy.
+    pub fn players(&self) -> (&Player, &Player) {
+        if self.player_one {
+            (&self.game.player_one, &self.game.player_two)
+        } else {
+            (&self.game.player_two, &self.game.player_one)
+        }
+    }
+}
+
 pub trait AI {
     fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn;
+
+    /// Same as `play`, with everything else known about the game. This is what `run` calls, AIs
+    /// that look at more than the position override it.
+    fn play_in_context(&mut self, context: &Context) -> Turn {
+        let (player, enemy) = context.players();
+        self.play(&context.game.board, player, enemy)
+    }
+
+    /// Called before the first turn of every game, `context` tells which player the AI is.
+    #[allow(unused_variables)]
+    fn new_game(&mut self, context: &Context) {}
+
+    /// The enemy played `turn`, which was legal.
+    #[allow(unused_variables)]
+    fn opponent_played(&mut self, turn: Turn) {}
+
+    /// Called once the game is over, however it ended.
+    #[allow(unused_variables)]
+    fn game_over(&mut self, result: &GameResult) {}
+
+    /// The enemy turn to think about while the enemy is thinking, asked for right after `play`.
+    /// AIs that don't ponder return `None` and never hear of pondering again.
+    fn ponder_turn(&self) -> Option<Turn> {
+        None
+    }
+
+    /// Starts thinking about the position after `predicted`, the turn returned by `ponder_turn`.
+    /// The position is the one `play` gets if the enemy plays `predicted`. Pondering has to
+    /// happen in the background, the game goes on while this AI ponders.
+    #[allow(unused_variables)]
+    fn ponder_start(&mut self, board: &Board, player: &Player, enemy: &Player, predicted: Turn) {}
+
+    /// The enemy played the predicted turn, `play` follows for the pondered position.
+    fn ponder_hit(&mut self) {}
+
+    /// The enemy played something else or the game ended, the pondering is wasted.
+    fn ponder_miss(&mut self) {}
+
+    /// A short name for reports, by default the name of the type without its path and generic
+    /// parameters.
+    fn name(&self) -> String {
+        let name = any::type_name::<Self>();
+        let name = name.split('<').next().unwrap_or(name);
+        name.rsplit("::").next().unwrap_or(name).to_string()
+    }
+}
+
+impl<A: AI + ?Sized> AI for &mut A {
+    fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn {
+        (**self).play(board, player, enemy)
+    }
+
+    fn play_in_context(&mut self, context: &Context) -> Turn {
+        (**self).play_in_context(context)
+    }
+
+    fn new_game(&mut self, context: &Context) {
+        (**self).new_game(context)
+    }
+
+    fn opponent_played(&mut self, turn: Turn) {
+        (**self).opponent_played(turn)
+    }
+
+    fn game_over(&mut self, result: &GameResult) {
+        (**self).game_over(result)
+    }
+
+    fn ponder_turn(&self) -> Option<Turn> {
+        (**self).ponder_turn()
+    }
+
+    fn ponder_start(&mut self, board: &Board, player: &Player, enemy: &Player, predicted: Turn) {
+        (**self).ponder_start(board, player, enemy, predicted)
+    }
+
+    fn ponder_hit(&mut self) {
+        (**self).ponder_hit()
+    }
+
+    fn ponder_miss(&mut self) {
+        (**self).ponder_miss()
+    }
+
+    fn name(&self) -> String {
+        (**self).name()
+    }
+}
+
+impl<A: AI + ?Sized> AI for Box<A> {
+    fn play(&mut self, board: &Board, player: &Player, enemy: &Player) -> Turn {
+        (**self).play(board, player, enemy)
+    }
+
+    fn play_in_context(&mut self, context: &Context) -> Turn {
+        (**self).play_in_context(context)
+    }
+
+    fn new_game(&mut self, context: &Context) {
+        (**self).new_game(context)
+    }
+
+    fn opponent_played(&mut self, turn: Turn) {
+        (**self).opponent_played(turn)
+    }
+
+    fn game_over(&mut self, result: &GameResult) {
+        (**self).game_over(result)
+    }
+
+    fn ponder_turn(&self) -> Option<Turn> {
+        (**self).ponder_turn()
+    }
+
+    fn ponder_start(&mut self, board: &Board, player: &Player, enemy: &Player, predicted: Turn) {
+        (**self).ponder_start(board, player, enemy, predicted)
+    }
+
+    fn ponder_hit(&mut self) {
+        (**self).ponder_hit()
+    }
+
+    fn ponder_miss(&mut self) {
+        (**self).ponder_miss()
+    }
+
+    fn name(&self) -> String {
+        (**self).name()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameEnd {
+    /// The winner reached their goal row.
+    Goal,
+    /// The loser played an illegal turn and forfeited.
+    IllegalTurn(Turn),
+    /// Neither player had walls left and the loser couldn't have won the race anymore.
+    Adjudicated,
+    /// The loser ran out of time, see `RunOptions::clock`.
+    Timeout,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct GameResult {
     pub turns: usize,
     pub winner: bool,
+    pub end: GameEnd,
+}
+
+/// How `run_with_options` plays a game.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RunOptions {
+    /// Ends the game as soon as neither player has walls left and the race is decided, see
+    /// `race::RaceTable`.
+    pub adjudicate_races: bool,
+    /// The time each player has for all their turns together. A player taking longer loses.
+    pub clock: Option<Duration>,
 }
 
-pub fn run<F, V>(mut player_one_ai: F, mut player_two_ai: V) -> GameResult
+pub fn run<F, V>(player_one_ai: F, player_two_ai: V) -> GameResult
 where
     F: AI,
     V: AI,
 {
-    let mut board = board::create_new_board();
-    let mut turns = 0;
-
-    let (mut player_one, mut player_two) = create_two_players();
+    run_with_observers(player_one_ai, player_two_ai, &mut [])
+}
 
-    loop {
-        let turn = player_one_ai.play(&board, &player_one, &player_two);
+/// Same as `run`, but reports everything that happens in the game to `observers`.
+pub fn run_with_observers<F, V>(
+    player_one_ai: F,
+    player_two_ai: V,
+    observers: &mut [&mut dyn GameObserver],
+) -> GameResult
+where
+    F: AI,
+    V: AI,
+{
+    run_with_options(
+        player_one_ai,
+        player_two_ai,
+        &RunOptions::default(),
+        observers,
+    )
+}
 
-        match turn {
-            Turn::Move((x, y)) => {
-                player_one.x = x;
-                player_one.y = y;
-            }
-            Turn::Wall(wall) => {
-                player_one.walls -= 1;
-                board::place_wall(&mut board, wall);
-            }
-        }
+/// Same as `run_with_observers`, played by `options`.
+pub fn run_with_options<F, V>(
+    mut player_one_ai: F,
+    mut player_two_ai: V,
+    options: &RunOptions,
+    observers: &mut [&mut dyn GameObserver],
+) -> GameResult
+where
+    F: AI,
+    V: AI,
+{
+    let mut game = Game::new();
+    let mut history = Vec::new();
+    let mut clocks = [options.clock; 2];
+    // The player pondering and the turn they predicted
+    let mut pondering: Option<(bool, Turn)> = None;
+    // The board doesn't change anymore once it's a race
+    let mut race_table: Option<RaceTable> = None;
 
-        turns += 1;
+    for observer in observers.iter_mut() {
+        observer.game_start(&game);
+    }
+    for (ai, player_one) in [
+        (&mut player_one_ai as &mut dyn AI, true),
+        (&mut player_two_ai, false),
+    ] {
+        ai.new_game(&Context {
+            game: &game,
+            history: &history,
+            player_one,
+            options,
+            time_left: clocks[!player_one as usize],
+        });
+    }
 
-        #[cfg(feature = "print_game")]
-        print(&board, &player_one, &player_two);
+    let result = loop {
+        let player_one = game.player_one_turn;
+        let clock = &mut clocks[!player_one as usize];
+        let context = Context {
+            game: &game,
+            history: &history,
+            player_one,
+            options,
+            time_left: *clock,
+        };
+        let start = Instant::now();
+        let turn = if player_one {
+            player_one_ai.play_in_context(&context)
+        } else {
+            player_two_ai.play_in_context(&context)
+        };
+        let elapsed = start.elapsed();
 
-        if player_one.y == board::get_board_height() - 1 {
-            return GameResult {
-                turns,
-                winner: true,
+        if let Some((ponderer, predicted)) = pondering.take() {
+            let ai: &mut dyn AI = if ponderer {
+                &mut player_one_ai
+            } else {
+                &mut player_two_ai
             };
-        }
 
-        let turn = player_two_ai.play(&board, &player_two, &player_one);
+            if turn == predicted && game.is_legal(turn) {
+                ai.ponder_hit();
+            } else {
+                ai.ponder_miss();
+            }
+        }
 
-        match turn {
-            Turn::Move((x, y)) => {
-                player_two.x = x;
-                player_two.y = y;
+        if let Some(time_left) = clock {
+            match time_left.checked_sub(elapsed) {
+                Some(left) => *time_left = left,
+                None => {
+                    break GameResult {
+                        turns: history.len(),
+                        winner: !player_one,
+                        end: GameEnd::Timeout,
+                    }
+                }
             }
-            Turn::Wall(wall) => {
-                player_two.walls -= 1;
-                board::place_wall(&mut board, wall);
+        }
+
+        if !game.is_legal(turn) {
+            for observer in observers.iter_mut() {
+                observer.illegal_turn(&game, turn);
             }
+
+            break GameResult {
+                turns: history.len(),
+                winner: !player_one,
+                end: GameEnd::IllegalTurn(turn),
+            };
         }
 
-        turns += 1;
+        game.apply(turn);
+        history.push(turn);
+        if player_one {
+            player_two_ai.opponent_played(turn);
+        } else {
+            player_one_ai.opponent_played(turn);
+        }
 
-        #[cfg(feature = "print_game")]
-        print(&board, &player_one, &player_two);
+        for observer in observers.iter_mut() {
+            observer.turn_played(&game, turn, elapsed);
+        }
 
-        if player_two.y == 0 {
-            return GameResult {
-                turns,
-                winner: false,
+        if let Some(winner) = game.winner() {
+            break GameResult {
+                turns: history.len(),
+                winner,
+                end: GameEnd::Goal,
             };
         }
-    }
-}
 
-pub(crate) fn create_two_players() -> (Player, Player) {
-    (
-        Player::new(4, 0, board::get_board_height() - 1),
-        Player::new(4, 8, 0),
-    )
-}
-
-#[cfg(feature = "print_game")]
-fn print(board: &Board, player_one: &Player, player_two: &Player) {
-    print!("┌");
-    for _ in 0..board::get_board_width() - 1 {
-        print!("───");
-        print!("┬");
-    }
-    print!("───");
-    println!("┐");
-
-    for row in 0..board::get_board_height() {
-        print!("│");
-        for col in 0..board::get_board_width() {
-            let user = if player_one.y == row && player_one.x == col {
-                "x"
-            } else if player_two.y == row && player_two.x == col {
-                "o"
-            } else {
-                " "
+        if options.adjudicate_races && race::is_race(&game) {
+            let table = race_table.get_or_insert_with(|| RaceTable::new(&game.board));
+            let winner = match table.outcome(&game) {
+                Outcome::Win(_) => Some(game.player_one_turn),
+                Outcome::Loss(_) => Some(!game.player_one_turn),
+                Outcome::Draw => None,
             };
-            print!(" {} ", user);
-            if col != board::get_board_width() - 1 {
-                print!(
-                    "{}",
-                    if !board[board::point_to_index(col, row)].right {
-                        "│"
-                    } else {
-                        " "
-                    }
-                );
+
+            if let Some(winner) = winner {
+                break GameResult {
+                    turns: history.len(),
+                    winner,
+                    end: GameEnd::Adjudicated,
+                };
             }
         }
-        print!("│");
-        println!();
-
-        if row != board::get_board_height() - 1 {
-            print!("├");
-            for col in 0..board::get_board_width() {
-                print!(
-                    "{}",
-                    if !board[board::point_to_index(col, row)].down {
-                        "───"
-                    } else {
-                        "   "
-                    }
-                );
-                if col != board::get_board_width() - 1 {
-                    print!("┼");
-                }
+
+        let ai: &mut dyn AI = if player_one {
+            &mut player_one_ai
+        } else {
+            &mut player_two_ai
+        };
+        if let Some(predicted) = ai.ponder_turn().filter(|&turn| game.is_legal(turn)) {
+            let mut next = game.clone();
+            next.apply(predicted);
+
+            if next.winner().is_none() {
+                let (player, enemy) = if player_one {
+                    (&next.player_one, &next.player_two)
+                } else {
+                    (&next.player_two, &next.player_one)
+                };
+                ai.ponder_start(&next.board, player, enemy, predicted);
+                pondering = Some((player_one, predicted));
             }
-            print!("┤");
-            println!();
+        }
+    };
+
+    // Nothing left to ponder about
+    if let Some((ponderer, _)) = pondering {
+        if ponderer {
+            player_one_ai.ponder_miss();
+        } else {
+            player_two_ai.ponder_miss();
         }
     }
 
-    print!("└");
-    for _ in 0..board::get_board_width() - 1 {
-        print!("───");
-        print!("┴");
+    player_one_ai.game_over(&result);
+    player_two_ai.game_over(&result);
+
+    for observer in observers.iter_mut() {
+        observer.game_end(&game, &result);
     }
-    print!("───");
-    println!("┘");
 
-    println!();
+    result
+}
 
-    thread::sleep(Duration::from_secs(1));
+pub(crate) fn create_two_players() -> (Player, Player) {
+    (
+        Player::new(4, 0, board::get_board_height() - 1),
+        Player::new(4, 8, 0),
+    )
 }
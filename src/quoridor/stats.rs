@@ -0,0 +1,262 @@
+//! Numbers over many games: where walls are placed and when, where the pawns go, how long games
+//! take and how often the first player wins.
+
+use super::{
+    board,
+    game::{Game, IllegalTurn},
+    observer::GameObserver,
+    run::{GameResult, Turn, WallData},
+};
+
+use std::{fmt::Write, fs, io, path::Path, time::Duration};
+
+/// Collects statistics from game records with `add_game` or, as an observer, from games while
+/// they are played.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statistics {
+    pub games: usize,
+    /// Games that ended with a winner, games from records may be unfinished.
+    pub decided: usize,
+    pub player_one_wins: usize,
+    /// Turns of both players in all games.
+    pub turns: usize,
+    /// Walls placed on every slot, see `wall_slot`.
+    walls: Vec<u64>,
+    /// Walls placed by both players at every ply.
+    wall_plies: Vec<[u64; 2]>,
+    /// How often both pawns stood on every tile, including the starting tiles.
+    tiles: Vec<[u64; 2]>,
+    /// The ply of the game being observed.
+    ply: usize,
+}
+
+/// Index of `wall` in `Statistics::walls`: horizontal walls row by row, then the vertical ones.
+fn wall_slot((vert, x, y): WallData) -> usize {
+    let width = board::get_board_width() - 1;
+    let height = board::get_board_height() - 1;
+
+    vert as usize * width * height + y * width + x
+}
+
+fn slot_wall(slot: usize) -> WallData {
+    let width = board::get_board_width() - 1;
+    let height = board::get_board_height() - 1;
+    let index = slot % (width * height);
+
+    (slot >= width * height, index % width, index / width)
+}
+
+impl Statistics {
+    pub fn new() -> Self {
+        let slots = 2 * (board::get_board_width() - 1) * (board::get_board_height() - 1);
+
+        Self {
+            games: 0,
+            decided: 0,
+            player_one_wins: 0,
+            turns: 0,
+            walls: vec![0; slots],
+            wall_plies: Vec::new(),
+            tiles: vec![[0; 2]; board::get_board_size()],
+            ply: 0,
+        }
+    }
+
+    /// Adds the game `turns`, won by player one if `winner` is `Some(true)`. Without a winner it
+    /// is taken from the final position, if there is one.
+    pub fn add_game(&mut self, turns: &[Turn], winner: Option<bool>) -> Result<(), IllegalTurn> {
+        // Don't count anything of an illegal record
+        Game::replay(turns)?;
+
+        let mut game = Game::new();
+        self.start(&game);
+        for &turn in turns {
+            game.apply(turn);
+            self.turn(&game, turn);
+        }
+        self.end(winner.or(game.winner()));
+
+        Ok(())
+    }
+
+    pub fn average_length(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.turns as f64 / self.games as f64
+        }
+    }
+
+    /// Share of the decided games won by player one.
+    pub fn player_one_win_rate(&self) -> f64 {
+        if self.decided == 0 {
+            0.0
+        } else {
+            self.player_one_wins as f64 / self.decided as f64
+        }
+    }
+
+    /// How often `wall` was placed, 0 for walls off the board.
+    pub fn walls_placed(&self, wall: WallData) -> u64 {
+        let (_, x, y) = wall;
+        if x >= board::get_board_width() - 1 || y >= board::get_board_height() - 1 {
+            return 0;
+        }
+
+        self.walls[wall_slot(wall)]
+    }
+
+    /// How often the players placed a wall with the `ply`th turn of a game (counting both
+    /// players' turns from 0), player one first.
+    pub fn walls_at_ply(&self, ply: usize) -> [u64; 2] {
+        self.wall_plies.get(ply).copied().unwrap_or_default()
+    }
+
+    /// How often the pawns of player one and two stood on the tile `(x, y)`, 0 for tiles off the
+    /// board.
+    pub fn tile_visits(&self, x: usize, y: usize) -> [u64; 2] {
+        if x >= board::get_board_width() || y >= board::get_board_height() {
+            return [0, 0];
+        }
+
+        self.tiles[board::point_to_index(x, y)]
+    }
+
+    /// Every wall slot with how often it was used relative to the most used one.
+    pub fn wall_heat(&self) -> Vec<(WallData, f64)> {
+        let max = self.walls.iter().copied().max().unwrap_or(0).max(1) as f64;
+
+        self.walls
+            .iter()
+            .enumerate()
+            .map(|(slot, &count)| (slot_wall(slot), count as f64 / max))
+            .collect()
+    }
+
+    /// Every tile by index with how often pawns stood on it relative to the busiest tile, for
+    /// both pawns or only the one of player one (`Some(true)`) or two (`Some(false)`).
+    pub fn tile_heat(&self, player_one: Option<bool>) -> Vec<f64> {
+        let visits: Vec<u64> = self
+            .tiles
+            .iter()
+            .map(|&[one, two]| match player_one {
+                None => one + two,
+                Some(true) => one,
+                Some(false) => two,
+            })
+            .collect();
+        let max = visits.iter().copied().max().unwrap_or(0).max(1) as f64;
+
+        visits.iter().map(|&count| count as f64 / max).collect()
+    }
+
+    /// One row: `games,decided,player_one_wins,player_one_win_rate,average_length`.
+    pub fn summary_csv(&self) -> String {
+        format!(
+            "games,decided,player_one_wins,player_one_win_rate,average_length\n{},{},{},{:.4},{:.2}\n",
+            self.games,
+            self.decided,
+            self.player_one_wins,
+            self.player_one_win_rate(),
+            self.average_length()
+        )
+    }
+
+    /// `vert,x,y,count` for every wall slot.
+    pub fn walls_csv(&self) -> String {
+        let mut out = String::from("vert,x,y,count\n");
+        for (slot, count) in self.walls.iter().enumerate() {
+            let (vert, x, y) = slot_wall(slot);
+            let _ = writeln!(out, "{},{},{},{}", vert, x, y, count);
+        }
+
+        out
+    }
+
+    /// `ply,player_one,player_two` for every ply a wall was placed at or before.
+    pub fn wall_plies_csv(&self) -> String {
+        let mut out = String::from("ply,player_one,player_two\n");
+        for (ply, [one, two]) in self.wall_plies.iter().enumerate() {
+            let _ = writeln!(out, "{},{},{}", ply, one, two);
+        }
+
+        out
+    }
+
+    /// `x,y,player_one,player_two` for every tile.
+    pub fn tiles_csv(&self) -> String {
+        let mut out = String::from("x,y,player_one,player_two\n");
+        for (index, [one, two]) in self.tiles.iter().enumerate() {
+            let (x, y) = board::index_to_point(index);
+            let _ = writeln!(out, "{},{},{},{}", x, y, one, two);
+        }
+
+        out
+    }
+
+    /// Writes `summary.csv`, `walls.csv`, `wall_plies.csv` and `tiles.csv` to `directory`.
+    pub fn write_csv(&self, directory: &Path) -> io::Result<()> {
+        fs::create_dir_all(directory)?;
+        fs::write(directory.join("summary.csv"), self.summary_csv())?;
+        fs::write(directory.join("walls.csv"), self.walls_csv())?;
+        fs::write(directory.join("wall_plies.csv"), self.wall_plies_csv())?;
+        fs::write(directory.join("tiles.csv"), self.tiles_csv())
+    }
+
+    fn start(&mut self, game: &Game) {
+        self.ply = 0;
+        self.visit(game.player_one.x, game.player_one.y, 0);
+        self.visit(game.player_two.x, game.player_two.y, 1);
+    }
+
+    /// `game` is the position after `turn`.
+    fn turn(&mut self, game: &Game, turn: Turn) {
+        let player = game.player_one_turn as usize;
+
+        match turn {
+            Turn::Move((x, y)) => self.visit(x, y, player),
+            Turn::Wall(wall) => {
+                self.walls[wall_slot(wall)] += 1;
+                if self.wall_plies.len() <= self.ply {
+                    self.wall_plies.resize(self.ply + 1, [0; 2]);
+                }
+                self.wall_plies[self.ply][player] += 1;
+            }
+        }
+
+        self.ply += 1;
+        self.turns += 1;
+    }
+
+    fn end(&mut self, winner: Option<bool>) {
+        self.games += 1;
+        if let Some(winner) = winner {
+            self.decided += 1;
+            self.player_one_wins += winner as usize;
+        }
+    }
+
+    fn visit(&mut self, x: usize, y: usize, player: usize) {
+        self.tiles[board::point_to_index(x, y)][player] += 1;
+    }
+}
+
+impl Default for Statistics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameObserver for Statistics {
+    fn game_start(&mut self, game: &Game) {
+        self.start(game);
+    }
+
+    fn turn_played(&mut self, game: &Game, turn: Turn, _elapsed: Duration) {
+        self.turn(game, turn);
+    }
+
+    fn game_end(&mut self, _game: &Game, result: &GameResult) {
+        self.end(Some(result.winner));
+    }
+}
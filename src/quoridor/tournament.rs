@@ -0,0 +1,113 @@
+use super::run::{run, GameResult, AI};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::fmt;
+
+/// A single game of a match along with the seeds both AIs were created with, so it can be
+/// replayed with `play_game`.
+#[derive(Clone, Copy, Debug)]
+pub struct MatchGame {
+    pub seeds: (u64, u64),
+    pub result: GameResult,
+}
+
+pub struct MatchResult {
+    pub seed: u64,
+    /// `AI::name` of both players.
+    pub names: (String, String),
+    pub games: Vec<MatchGame>,
+}
+
+impl MatchResult {
+    pub fn player_one_wins(&self) -> usize {
+        self.games.iter().filter(|game| game.result.winner).count()
+    }
+
+    pub fn player_two_wins(&self) -> usize {
+        self.games.len() - self.player_one_wins()
+    }
+}
+
+impl fmt::Display for MatchResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "match seed {}", self.seed)?;
+        for (index, game) in self.games.iter().enumerate() {
+            writeln!(
+                f,
+                "game {:>3}: seeds {} {}, player {} won after {} turns",
+                index + 1,
+                game.seeds.0,
+                game.seeds.1,
+                if game.result.winner { "one" } else { "two" },
+                game.result.turns
+            )?;
+        }
+        write!(
+            f,
+            "{} {} - {} {}",
+            self.names.0,
+            self.player_one_wins(),
+            self.player_two_wins(),
+            self.names.1
+        )
+    }
+}
+
+/// Creates both AIs from `seeds` and plays a game between them.
+pub fn play_game<F, V>(
+    seeds: (u64, u64),
+    player_one: impl FnOnce(u64) -> F,
+    player_two: impl FnOnce(u64) -> V,
+) -> GameResult
+where
+    F: AI,
+    V: AI,
+{
+    play_named(seeds, player_one, player_two).1
+}
+
+/// `play_game`, also returning the `AI::name`s of both players.
+fn play_named<F, V>(
+    seeds: (u64, u64),
+    player_one: impl FnOnce(u64) -> F,
+    player_two: impl FnOnce(u64) -> V,
+) -> ((String, String), GameResult)
+where
+    F: AI,
+    V: AI,
+{
+    let (one, two) = (player_one(seeds.0), player_two(seeds.1));
+    let names = (one.name(), two.name());
+
+    (names, run(one, two))
+}
+
+/// Plays `games` games, creating fresh AIs from per-game seeds derived from `seed`. The same
+/// seed always produces the same match.
+pub fn play_match<F, V>(
+    games: usize,
+    seed: u64,
+    mut player_one: impl FnMut(u64) -> F,
+    mut player_two: impl FnMut(u64) -> V,
+) -> MatchResult
+where
+    F: AI,
+    V: AI,
+{
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut names = (String::from("player one"), String::from("player two"));
+
+    let games = (0..games)
+        .map(|game| {
+            let seeds = (rng.gen(), rng.gen());
+            let (game_names, result) = play_named(seeds, &mut player_one, &mut player_two);
+            if game == 0 {
+                names = game_names;
+            }
+
+            MatchGame { seeds, result }
+        })
+        .collect();
+
+    MatchResult { seed, names, games }
+}
@@ -0,0 +1,43 @@
+use super::{
+    grid::{self, Glyphs},
+    Renderer,
+};
+use crate::quoridor::{board::Board, player::Player, run::Turn};
+
+const GLYPHS: Glyphs = Glyphs {
+    top: ["+", "+", "+"],
+    middle: ["+", "+", "+"],
+    bottom: ["+", "+", "+"],
+    horizontal: "---",
+    vertical: "|",
+    highlighted_horizontal: "###",
+    highlighted_vertical: "#",
+};
+
+/// Same layout as `unicode::Unicode` for terminals without box drawing characters, the last
+/// wall is drawn with `#`.
+#[derive(Clone, Copy, Default)]
+pub struct Ascii {
+    pub labels: bool,
+    pub highlight: bool,
+}
+
+impl Renderer for Ascii {
+    fn render(
+        &self,
+        board: &Board,
+        player_one: &Player,
+        player_two: &Player,
+        last_turn: Option<Turn>,
+    ) -> String {
+        grid::render(
+            &GLYPHS,
+            self.labels,
+            self.highlight,
+            board,
+            player_one,
+            player_two,
+            last_turn,
+        )
+    }
+}
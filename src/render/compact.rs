@@ -0,0 +1,78 @@
+use super::{
+    column_label, is_down_open, is_last_down_wall, is_last_right_wall, is_right_open, pawn,
+    row_label, Renderer,
+};
+use crate::quoridor::{
+    board::{self, Board},
+    player::Player,
+    run::Turn,
+};
+
+use std::fmt::Write;
+
+/// One line per row. Every tile is three characters: the pawn (`.` if there is none), `_` if
+/// there is a wall below and `|` if there is a wall to the right. The last moved pawn is drawn
+/// in upper case and the last wall with `=`/`!`.
+#[derive(Clone, Copy, Default)]
+pub struct Compact {
+    pub labels: bool,
+    pub highlight: bool,
+}
+
+impl Renderer for Compact {
+    fn render(
+        &self,
+        board: &Board,
+        player_one: &Player,
+        player_two: &Player,
+        last_turn: Option<Turn>,
+    ) -> String {
+        let width = board::get_board_width();
+        let last_turn = if self.highlight { last_turn } else { None };
+        let mut out = String::new();
+
+        if self.labels {
+            out.push_str("  ");
+            for x in 0..width {
+                let _ = write!(out, "{}  ", column_label(x));
+            }
+            out.truncate(out.trim_end().len());
+            out.push('\n');
+        }
+
+        for y in 0..board::get_board_height() {
+            if self.labels {
+                let _ = write!(out, "{:<2}", row_label(y));
+            }
+
+            for x in 0..width {
+                out.push(match pawn(player_one, player_two, last_turn, x, y) {
+                    Some((pawn, true)) => pawn.to_ascii_uppercase(),
+                    Some((pawn, false)) => pawn,
+                    None => '.',
+                });
+                out.push(
+                    if y == board::get_board_height() - 1 || is_down_open(board, x, y) {
+                        ' '
+                    } else if is_last_down_wall(last_turn, x, y) {
+                        '='
+                    } else {
+                        '_'
+                    },
+                );
+                out.push(if x == width - 1 || is_right_open(board, x, y) {
+                    ' '
+                } else if is_last_right_wall(last_turn, x, y) {
+                    '!'
+                } else {
+                    '|'
+                });
+            }
+
+            out.truncate(out.trim_end().len());
+            out.push('\n');
+        }
+
+        out
+    }
+}
@@ -0,0 +1,113 @@
+use super::{
+    column_label, is_down_open, is_last_down_wall, is_last_right_wall, is_right_open, pawn,
+    row_label,
+};
+use crate::quoridor::{
+    board::{self, Board},
+    player::Player,
+    run::Turn,
+};
+
+use std::fmt::Write;
+
+/// The characters a box drawn board is made of.
+pub(super) struct Glyphs {
+    pub top: [&'static str; 3],
+    pub middle: [&'static str; 3],
+    pub bottom: [&'static str; 3],
+    pub horizontal: &'static str,
+    pub vertical: &'static str,
+    pub highlighted_horizontal: &'static str,
+    pub highlighted_vertical: &'static str,
+}
+
+/// Draws every tile as a 3 character wide cell with the walls as lines between them.
+pub(super) fn render(
+    glyphs: &Glyphs,
+    labels: bool,
+    highlight: bool,
+    board: &Board,
+    player_one: &Player,
+    player_two: &Player,
+    last_turn: Option<Turn>,
+) -> String {
+    let width = board::get_board_width();
+    let height = board::get_board_height();
+    let last_turn = if highlight { last_turn } else { None };
+    let margin = if labels { "  " } else { "" };
+    let mut out = String::new();
+
+    if labels {
+        out.push_str("  ");
+        for x in 0..width {
+            let _ = write!(out, "  {} ", column_label(x));
+        }
+        out.truncate(out.trim_end().len());
+        out.push('\n');
+    }
+
+    let border = |out: &mut String, [left, middle, right]: [&str; 3]| {
+        out.push_str(margin);
+        out.push_str(left);
+        for x in 0..width {
+            out.push_str(glyphs.horizontal);
+            out.push_str(if x != width - 1 { middle } else { right });
+        }
+        out.push('\n');
+    };
+
+    border(&mut out, glyphs.top);
+
+    for y in 0..height {
+        if labels {
+            let _ = write!(out, "{:<2}", row_label(y));
+        }
+
+        out.push_str(glyphs.vertical);
+        for x in 0..width {
+            match pawn(player_one, player_two, last_turn, x, y) {
+                Some((pawn, true)) => {
+                    let _ = write!(out, "[{}]", pawn);
+                }
+                Some((pawn, false)) => {
+                    let _ = write!(out, " {} ", pawn);
+                }
+                None => out.push_str("   "),
+            }
+
+            out.push_str(if x == width - 1 {
+                glyphs.vertical
+            } else if is_right_open(board, x, y) {
+                " "
+            } else if is_last_right_wall(last_turn, x, y) {
+                glyphs.highlighted_vertical
+            } else {
+                glyphs.vertical
+            });
+        }
+        out.push('\n');
+
+        if y != height - 1 {
+            out.push_str(margin);
+            out.push_str(glyphs.middle[0]);
+            for x in 0..width {
+                out.push_str(if is_down_open(board, x, y) {
+                    "   "
+                } else if is_last_down_wall(last_turn, x, y) {
+                    glyphs.highlighted_horizontal
+                } else {
+                    glyphs.horizontal
+                });
+                if x != width - 1 {
+                    out.push_str(glyphs.middle[1]);
+                }
+            }
+            out.push_str(glyphs.middle[2]);
+            out.push('\n');
+        }
+    }
+
+    border(&mut out, glyphs.bottom);
+
+    out
+}
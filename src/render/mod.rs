@@ -0,0 +1,156 @@
+pub mod ascii;
+pub mod compact;
+pub mod svg;
+pub mod unicode;
+
+mod grid;
+
+use super::quoridor::{
+    board::{self, Board},
+    game::Game,
+    observer::GameObserver,
+    player::Player,
+    run::{GameResult, Turn},
+};
+
+use std::{fmt, thread, time::Duration};
+
+/// Turns a position into text.
+///
+/// `last_turn` is the turn that led to the position, backends highlight it if asked to.
+pub trait Renderer {
+    fn render(
+        &self,
+        board: &Board,
+        player_one: &Player,
+        player_two: &Player,
+        last_turn: Option<Turn>,
+    ) -> String;
+}
+
+impl<R: Renderer + ?Sized> Renderer for Box<R> {
+    fn render(
+        &self,
+        board: &Board,
+        player_one: &Player,
+        player_two: &Player,
+        last_turn: Option<Turn>,
+    ) -> String {
+        (**self).render(board, player_one, player_two, last_turn)
+    }
+}
+
+/// A board and its players, displayed with the default `unicode::Unicode` renderer.
+pub struct View<'a> {
+    pub board: &'a Board,
+    pub player_one: &'a Player,
+    pub player_two: &'a Player,
+}
+
+impl fmt::Display for View<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&unicode::Unicode::default().render(
+            self.board,
+            self.player_one,
+            self.player_two,
+            None,
+        ))
+    }
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        View {
+            board: &self.board,
+            player_one: &self.player_one,
+            player_two: &self.player_two,
+        }
+        .fmt(f)
+    }
+}
+
+/// Prints the position to stdout after every turn, waiting `delay` so a game can be watched.
+pub struct Printer<R: Renderer> {
+    pub renderer: R,
+    pub delay: Duration,
+}
+
+impl<R: Renderer> Printer<R> {
+    pub fn new(renderer: R, delay: Duration) -> Self {
+        Self { renderer, delay }
+    }
+}
+
+impl<R: Renderer> GameObserver for Printer<R> {
+    fn game_start(&mut self, game: &Game) {
+        println!(
+            "{}",
+            self.renderer
+                .render(&game.board, &game.player_one, &game.player_two, None)
+        );
+    }
+
+    fn turn_played(&mut self, game: &Game, turn: Turn, _elapsed: Duration) {
+        println!(
+            "{}",
+            self.renderer
+                .render(&game.board, &game.player_one, &game.player_two, Some(turn))
+        );
+
+        thread::sleep(self.delay);
+    }
+
+    fn game_end(&mut self, _game: &Game, result: &GameResult) {
+        println!(
+            "Player {} won after {} turns",
+            if result.winner { "x" } else { "o" },
+            result.turns
+        );
+    }
+}
+
+/// The letter of column `x`, as used by coordinate labels.
+pub fn column_label(x: usize) -> char {
+    (b'a' + x as u8) as char
+}
+
+/// The number of row `y`, as used by coordinate labels. Rows are counted from 1.
+pub fn row_label(y: usize) -> usize {
+    y + 1
+}
+
+fn pawn(
+    player_one: &Player,
+    player_two: &Player,
+    last_turn: Option<Turn>,
+    x: usize,
+    y: usize,
+) -> Option<(char, bool)> {
+    let highlighted = last_turn == Some(Turn::Move((x, y)));
+
+    if (player_one.x, player_one.y) == (x, y) {
+        Some(('x', highlighted))
+    } else if (player_two.x, player_two.y) == (x, y) {
+        Some(('o', highlighted))
+    } else {
+        None
+    }
+}
+
+/// Whether `last_turn` is a wall blocking the way right from `(x, y)`.
+fn is_last_right_wall(last_turn: Option<Turn>, x: usize, y: usize) -> bool {
+    matches!(last_turn, Some(Turn::Wall((true, w_x, w_y))) if w_x == x && (w_y == y || w_y + 1 == y))
+}
+
+/// Whether `last_turn` is a wall blocking the way down from `(x, y)`.
+fn is_last_down_wall(last_turn: Option<Turn>, x: usize, y: usize) -> bool {
+    matches!(last_turn, Some(Turn::Wall((false, w_x, w_y))) if w_y == y && (w_x == x || w_x + 1 == x))
+}
+
+fn is_right_open(board: &Board, x: usize, y: usize) -> bool {
+    board[board::point_to_index(x, y)].right
+}
+
+fn is_down_open(board: &Board, x: usize, y: usize) -> bool {
+    board[board::point_to_index(x, y)].down
+}
@@ -0,0 +1,395 @@
+use super::{column_label, row_label, Renderer};
+use crate::quoridor::{
+    board::{self, Board},
+    game::{Game, IllegalTurn},
+    player::Player,
+    run::{MoveData, Turn, WallData},
+};
+
+use std::{collections::VecDeque, fmt::Write, fs, io, path::Path, time::Duration};
+
+const TILE: usize = 40;
+const GAP: usize = 10;
+const STEP: usize = TILE + GAP;
+
+const PLAYER_ONE_COLOR: &str = "#d32f2f";
+const PLAYER_TWO_COLOR: &str = "#1976d2";
+const WALL_COLOR: &str = "#5d4037";
+const HIGHLIGHT_COLOR: &str = "#ffb300";
+const ARROW_COLOR: &str = "#2e7d32";
+const HEAT_COLOR: &str = "#c62828";
+
+/// Extra drawings on top of a position.
+#[derive(Clone, Default)]
+pub struct Annotations {
+    /// Drawn as an arrow for a move or a dashed outline for a wall, for the player to move.
+    pub suggested_turn: Option<Turn>,
+    /// Paths as returned by `board::get_path_to_goal`, drawn as arrows.
+    pub paths: Vec<VecDeque<MoveData>>,
+}
+
+/// Scalable vector graphics, for docs and bug reports.
+///
+/// Player one is red, player two blue. The last turn is highlighted in amber.
+#[derive(Clone, Copy, Default)]
+pub struct Svg {
+    pub labels: bool,
+    pub highlight: bool,
+}
+
+impl Renderer for Svg {
+    fn render(
+        &self,
+        board: &Board,
+        player_one: &Player,
+        player_two: &Player,
+        last_turn: Option<Turn>,
+    ) -> String {
+        let mut out = self.header();
+        self.write_pieces(&mut out, board, player_one, player_two, last_turn);
+        out.push_str("</svg>\n");
+
+        out
+    }
+}
+
+impl Svg {
+    /// Renders `game` with `annotations` drawn on top, suggestions are for the player to move.
+    pub fn render_annotated(
+        &self,
+        game: &Game,
+        last_turn: Option<Turn>,
+        annotations: &Annotations,
+    ) -> String {
+        let mut out = self.header();
+        self.write_pieces(
+            &mut out,
+            &game.board,
+            &game.player_one,
+            &game.player_two,
+            last_turn,
+        );
+
+        // Pieces and annotations are drawn relative to the board
+        let _ = writeln!(
+            out,
+            r#"<g transform="translate({0} {0})">"#,
+            margin(self.labels)
+        );
+
+        for path in &annotations.paths {
+            write_path(&mut out, path.iter().copied());
+        }
+
+        match annotations.suggested_turn {
+            Some(Turn::Move(to)) => {
+                let (player, _) = game.players();
+                write_path(&mut out, [(player.x, player.y), to].into_iter());
+            }
+            Some(Turn::Wall(wall)) => {
+                let (x, y, width, height) = wall_rect(wall);
+                let _ = writeln!(
+                    out,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="{}" stroke-width="2" stroke-dasharray="4 3"/>"#,
+                    x, y, width, height, ARROW_COLOR
+                );
+            }
+            None => {}
+        }
+
+        out.push_str("</g>\n</svg>\n");
+
+        out
+    }
+
+    /// A heatmap on an empty board: tiles (by index) and walls shaded by their value between 0
+    /// and 1, like `stats::Statistics::tile_heat` and `wall_heat` return them. Cold tiles and
+    /// walls are left as the empty board draws them.
+    pub fn heatmap(&self, tiles: &[f64], walls: &[(WallData, f64)]) -> String {
+        let mut out = self.header();
+        let _ = writeln!(
+            out,
+            r#"<g transform="translate({0} {0})">"#,
+            margin(self.labels)
+        );
+
+        for (index, &heat) in tiles.iter().enumerate().filter(|(_, &heat)| heat > 0.0) {
+            let (x, y) = board::index_to_point(index);
+            let _ = writeln!(
+                out,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" rx="3" fill="{}" fill-opacity="{:.3}"/>"#,
+                x * STEP,
+                y * STEP,
+                TILE,
+                TILE,
+                HEAT_COLOR,
+                heat.min(1.0)
+            );
+        }
+
+        for &(wall, heat) in walls.iter().filter(|(_, heat)| *heat > 0.0) {
+            let (x, y, width, height) = wall_rect(wall);
+            let _ = writeln!(
+                out,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" rx="2" fill="{}" fill-opacity="{:.3}"/>"#,
+                x,
+                y,
+                width,
+                height,
+                WALL_COLOR,
+                heat.min(1.0)
+            );
+        }
+
+        out.push_str("</g>\n</svg>\n");
+
+        out
+    }
+
+    /// Every position of the game started by `turns`, the first frame being the starting
+    /// position.
+    pub fn frames(&self, turns: &[Turn]) -> Result<Vec<String>, IllegalTurn> {
+        let mut game = Game::new();
+        let mut frames = vec![self.render(&game.board, &game.player_one, &game.player_two, None)];
+
+        for (index, &turn) in turns.iter().enumerate() {
+            if !game.is_legal(turn) {
+                return Err(IllegalTurn { index, turn });
+            }
+
+            game.apply(turn);
+            frames.push(self.render(&game.board, &game.player_one, &game.player_two, Some(turn)));
+        }
+
+        Ok(frames)
+    }
+
+    /// Writes `frames` to `directory` as `000.svg`, `001.svg`, ...
+    pub fn write_frames(&self, turns: &[Turn], directory: &Path) -> io::Result<()> {
+        let frames = self.frames(turns).map_err(|illegal| {
+            io::Error::new(io::ErrorKind::InvalidInput, format!("{:?}", illegal))
+        })?;
+
+        fs::create_dir_all(directory)?;
+        for (index, frame) in frames.iter().enumerate() {
+            fs::write(directory.join(format!("{:03}.svg", index)), frame)?;
+        }
+
+        Ok(())
+    }
+
+    /// A single looping SVG showing every position of the game for `frame` each.
+    pub fn animate(&self, turns: &[Turn], frame: Duration) -> Result<String, IllegalTurn> {
+        let frame_count = turns.len() + 1;
+        let total = frame.as_secs_f64() * frame_count as f64;
+        let mut game = Game::new();
+        let mut out = self.header();
+
+        for index in 0..frame_count {
+            let last_turn = if index == 0 {
+                None
+            } else {
+                let turn = turns[index - 1];
+                if !game.is_legal(turn) {
+                    return Err(IllegalTurn {
+                        index: index - 1,
+                        turn,
+                    });
+                }
+                game.apply(turn);
+                Some(turn)
+            };
+
+            let start = index as f64 / frame_count as f64;
+            let end = (index + 1) as f64 / frame_count as f64;
+            let (values, key_times) = match (index == 0, index == frame_count - 1) {
+                (true, true) => ("visible".to_string(), "0".to_string()),
+                (true, false) => ("visible;hidden".to_string(), format!("0;{:.6}", end)),
+                (false, true) => ("hidden;visible".to_string(), format!("0;{:.6}", start)),
+                (false, false) => (
+                    "hidden;visible;hidden".to_string(),
+                    format!("0;{:.6};{:.6}", start, end),
+                ),
+            };
+
+            let _ = writeln!(
+                out,
+                r#"<g visibility="hidden"><animate attributeName="visibility" values="{}" keyTimes="{}" dur="{:.3}s" calcMode="discrete" repeatCount="indefinite"/>"#,
+                values, key_times, total
+            );
+            self.write_pieces(
+                &mut out,
+                &game.board,
+                &game.player_one,
+                &game.player_two,
+                last_turn,
+            );
+            out.push_str("</g>\n");
+        }
+
+        out.push_str("</svg>\n");
+
+        Ok(out)
+    }
+
+    fn header(&self) -> String {
+        let width = margin(self.labels) + board::get_board_width() * STEP;
+        let height = margin(self.labels) + board::get_board_height() * STEP;
+        let mut out = String::new();
+
+        let _ = writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+            width, height
+        );
+        let _ = writeln!(
+            out,
+            r#"<defs><marker id="arrow" viewBox="0 0 10 10" refX="8" refY="5" markerWidth="5" markerHeight="5" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10 z" fill="{}"/></marker></defs>"#,
+            ARROW_COLOR
+        );
+        let _ = writeln!(
+            out,
+            r##"<rect width="{}" height="{}" fill="#f5f0e6"/>"##,
+            width, height
+        );
+
+        if self.labels {
+            for x in 0..board::get_board_width() {
+                let _ = writeln!(
+                    out,
+                    r#"<text x="{}" y="{}" font-family="sans-serif" font-size="14" text-anchor="middle">{}</text>"#,
+                    tile_x(x, true) + TILE / 2,
+                    margin(true) - 8,
+                    column_label(x)
+                );
+            }
+            for y in 0..board::get_board_height() {
+                let _ = writeln!(
+                    out,
+                    r#"<text x="{}" y="{}" font-family="sans-serif" font-size="14" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                    margin(true) / 2,
+                    tile_y(y, true) + TILE / 2,
+                    row_label(y)
+                );
+            }
+        }
+
+        for y in 0..board::get_board_height() {
+            for x in 0..board::get_board_width() {
+                let _ = writeln!(
+                    out,
+                    r##"<rect x="{}" y="{}" width="{}" height="{}" rx="3" fill="#c8b89a"/>"##,
+                    tile_x(x, self.labels),
+                    tile_y(y, self.labels),
+                    TILE,
+                    TILE
+                );
+            }
+        }
+
+        out
+    }
+
+    fn write_pieces(
+        &self,
+        out: &mut String,
+        board: &Board,
+        player_one: &Player,
+        player_two: &Player,
+        last_turn: Option<Turn>,
+    ) {
+        let last_turn = if self.highlight { last_turn } else { None };
+        let _ = writeln!(
+            out,
+            r#"<g transform="translate({0} {0})">"#,
+            margin(self.labels)
+        );
+
+        for wall in board::get_walls(board) {
+            let (x, y, width, height) = wall_rect(wall);
+            let color = if last_turn == Some(Turn::Wall(wall)) {
+                HIGHLIGHT_COLOR
+            } else {
+                WALL_COLOR
+            };
+            let _ = writeln!(
+                out,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" rx="2" fill="{}"/>"#,
+                x, y, width, height, color
+            );
+        }
+
+        for (player, color) in [
+            (player_one, PLAYER_ONE_COLOR),
+            (player_two, PLAYER_TWO_COLOR),
+        ] {
+            let (x, y) = centre((player.x, player.y));
+            let stroke = if last_turn == Some(Turn::Move((player.x, player.y))) {
+                format!(r#" stroke="{}" stroke-width="4""#, HIGHLIGHT_COLOR)
+            } else {
+                String::new()
+            };
+            let _ = writeln!(
+                out,
+                r#"<circle cx="{}" cy="{}" r="{}" fill="{}"{}/>"#,
+                x,
+                y,
+                TILE * 3 / 8,
+                color,
+                stroke
+            );
+        }
+
+        out.push_str("</g>\n");
+    }
+}
+
+fn margin(labels: bool) -> usize {
+    if labels {
+        24 + GAP
+    } else {
+        GAP
+    }
+}
+
+fn tile_x(x: usize, labels: bool) -> usize {
+    margin(labels) + x * STEP
+}
+
+fn tile_y(y: usize, labels: bool) -> usize {
+    margin(labels) + y * STEP
+}
+
+/// Centre of a tile, relative to the board.
+fn centre((x, y): MoveData) -> (usize, usize) {
+    (x * STEP + TILE / 2, y * STEP + TILE / 2)
+}
+
+/// `(x, y, width, height)` of a wall, relative to the board.
+fn wall_rect((vert, x, y): WallData) -> (usize, usize, usize, usize) {
+    if vert {
+        (x * STEP + TILE, y * STEP, GAP, 2 * TILE + GAP)
+    } else {
+        (x * STEP, y * STEP + TILE, 2 * TILE + GAP, GAP)
+    }
+}
+
+fn write_path(out: &mut String, path: impl Iterator<Item = MoveData>) {
+    let points: Vec<String> = path
+        .map(|point| {
+            let (x, y) = centre(point);
+            format!("{},{}", x, y)
+        })
+        .collect();
+
+    if points.len() < 2 {
+        return;
+    }
+
+    let _ = writeln!(
+        out,
+        r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="3" stroke-linejoin="round" marker-end="url(#arrow)"/>"#,
+        points.join(" "),
+        ARROW_COLOR
+    );
+}